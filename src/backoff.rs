@@ -60,9 +60,8 @@ fn random_u7() -> u8 {
 }
 
 /// Generates a pseudo u32 random number using atomics with LCG like algorithm same as random_u8
-#[allow(dead_code)]
 #[inline(always)]
-fn random_u32() -> u32 {
+pub fn random_u32() -> u32 {
     static SEED: AtomicU32 = AtomicU32::new(13);
     const MULTIPLIER: u32 = 1812433253;
     let seed = SEED.fetch_add(1, Ordering::SeqCst);
@@ -0,0 +1,636 @@
+use std::{collections::VecDeque, fmt, fmt::Debug, sync::Arc};
+#[cfg(feature = "async")]
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+#[cfg(not(feature = "std-mutex"))]
+use crate::mutex::{Mutex, MutexGuard};
+#[cfg(feature = "std-mutex")]
+use std::sync::{Mutex, MutexGuard};
+
+use crate::{
+    pointer::KanalPtr, signal::*, waitlist::WaitList, BroadcastReceiveError, BroadcastTryRecvError,
+    SendError,
+};
+
+type BroadcastShared<T> = Arc<Mutex<BroadcastInternal<T>>>;
+
+/// Acquire mutex guard on broadcast internal for use in channel operations
+#[inline(always)]
+fn acquire_broadcast<T>(internal: &'_ BroadcastShared<T>) -> MutexGuard<'_, BroadcastInternal<T>> {
+    #[cfg(not(feature = "std-mutex"))]
+    return internal.lock();
+    #[cfg(feature = "std-mutex")]
+    internal.lock().unwrap()
+}
+
+/// Internal of the broadcast channel, it keeps the latest objects in a ring buffer,
+///  and every receiver tracks its own position in the stream of sent objects.
+struct BroadcastInternal<T> {
+    /// Ring buffer of the latest sent objects
+    buffer: VecDeque<T>,
+    /// Position of the first object of the buffer in the stream of sent objects
+    head: u64,
+    /// The capacity of the ring buffer
+    capacity: usize,
+    /// Receivers that are waiting for a new object, they are notified with an empty object
+    waiters: WaitList<()>,
+    /// Count of alive receivers
+    recv_count: u32,
+    /// Count of alive senders
+    send_count: u32,
+}
+
+impl<T> BroadcastInternal<T> {
+    fn new(capacity: usize) -> BroadcastShared<T> {
+        assert!(
+            capacity > 0,
+            "broadcast channel capacity must be at least 1"
+        );
+        Arc::new(Mutex::from(Self {
+            buffer: VecDeque::with_capacity(capacity),
+            head: 0,
+            capacity,
+            waiters: WaitList::new(),
+            recv_count: 1,
+            send_count: 1,
+        }))
+    }
+
+    /// Returns the position of the next object that is going to be sent
+    #[inline(always)]
+    fn tail(&self) -> u64 {
+        self.head + self.buffer.len() as u64
+    }
+
+    /// Returns the next object for a receiver that is at the `next` position, and moves the position forward
+    fn next_for(&self, next: &mut u64) -> Result<T, BroadcastTryRecvError>
+    where
+        T: Clone,
+    {
+        if self.recv_count == 0 {
+            return Err(BroadcastTryRecvError::Closed);
+        }
+        if *next < self.head {
+            // the receiver missed the overwritten objects, continue from the oldest available one
+            let missed = self.head - *next;
+            *next = self.head;
+            return Err(BroadcastTryRecvError::Lagged(missed));
+        }
+        if *next < self.tail() {
+            let v = self.buffer[(*next - self.head) as usize].clone();
+            *next += 1;
+            return Ok(v);
+        }
+        if self.send_count == 0 {
+            return Err(BroadcastTryRecvError::SendClosed);
+        }
+        Err(BroadcastTryRecvError::Empty)
+    }
+
+    /// Returns the count of objects that are available for a receiver at the `next` position
+    fn pending_for(&self, next: u64) -> usize {
+        (self.tail() - next.max(self.head)) as usize
+    }
+
+    /// Adds the signal of a receiver to the waiters
+    #[inline(always)]
+    fn push_waiter(&mut self, sig: Signal<()>) {
+        // Safety: waiters keep their signals in place until they are removed from the waiters
+        unsafe { self.waiters.push_back(sig) }
+    }
+
+    /// Tries to remove the signal of a receiver from the waiters, returns true if the operation was successful
+    #[cfg(feature = "async")]
+    #[inline(always)]
+    fn cancel_waiter(&mut self, sig: Signal<()>) -> bool {
+        // Safety: the owner of the signal cancels it, and it's only linked in the waiters of this channel
+        unsafe { self.waiters.remove(sig) }
+    }
+
+    /// Checks if the signal of a receiver is in the waiters
+    #[cfg(feature = "async")]
+    #[inline(always)]
+    fn waiter_exists(&self, sig: Signal<()>) -> bool {
+        // Safety: the owner of the signal checks it, and it's only linked in the waiters of this channel
+        unsafe { self.waiters.contains(sig) }
+    }
+
+    /// Terminates waiting receivers, so they check the state of the channel again
+    fn terminate_waiters(&mut self) {
+        while let Some(sig) = self.waiters.pop_front() {
+            // Safety: it's safe to terminate owned signal once
+            unsafe { sig.terminate() }
+        }
+    }
+}
+
+/// Converts the result of a receive attempt that found an object or an error, and not an empty channel
+#[inline(always)]
+fn into_receive_error(err: BroadcastTryRecvError) -> BroadcastReceiveError {
+    match err {
+        BroadcastTryRecvError::Lagged(n) => BroadcastReceiveError::Lagged(n),
+        BroadcastTryRecvError::Closed => BroadcastReceiveError::Closed,
+        BroadcastTryRecvError::SendClosed => BroadcastReceiveError::SendClosed,
+        BroadcastTryRecvError::Empty => unreachable!("bug: empty broadcast is not a receive error"),
+    }
+}
+
+macro_rules! broadcast_shared_impl {
+    () => {
+        /// Returns capacity of the broadcast ring buffer
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::broadcast::<u64>(16);
+        /// assert_eq!(s.capacity(),16);
+        /// assert_eq!(r.capacity(),16);
+        /// ```
+        pub fn capacity(&self) -> usize {
+            acquire_broadcast(&self.internal).capacity
+        }
+        /// Returns count of alive receiver instances of the channel
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::broadcast::<u64>(16);
+        /// let receiver_clone=r.clone();
+        /// assert_eq!(s.receiver_count(),2);
+        /// ```
+        pub fn receiver_count(&self) -> u32 {
+            acquire_broadcast(&self.internal).recv_count
+        }
+        /// Returns count of alive sender instances of the channel
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::broadcast::<u64>(16);
+        /// let sender_clone=s.clone();
+        /// assert_eq!(r.sender_count(),2);
+        /// ```
+        pub fn sender_count(&self) -> u32 {
+            acquire_broadcast(&self.internal).send_count
+        }
+        /// Closes the channel completely on both sides, drops the buffered objects and terminates waiting receivers
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::broadcast::<u64>(16);
+        /// s.close();
+        /// assert_eq!(r.is_closed(),true);
+        /// ```
+        pub fn close(&self) -> bool {
+            let mut internal = acquire_broadcast(&self.internal);
+            if internal.recv_count == 0 && internal.send_count == 0 {
+                return false;
+            }
+            internal.recv_count = 0;
+            internal.send_count = 0;
+            internal.terminate_waiters();
+            let buffer = std::mem::take(&mut internal.buffer);
+            drop(internal);
+            // drop buffered objects outside of the lock
+            drop(buffer);
+            true
+        }
+        /// Returns whether the channel is closed on both side of send and receive or not
+        pub fn is_closed(&self) -> bool {
+            let internal = acquire_broadcast(&self.internal);
+            internal.send_count == 0 && internal.recv_count == 0
+        }
+    };
+}
+
+macro_rules! broadcast_recv_impl {
+    () => {
+        /// Returns count of objects that are available for this receiver
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::broadcast(16);
+        /// s.send(1)?;
+        /// s.send(2)?;
+        /// assert_eq!(r.len(),2);
+        /// # anyhow::Ok(())
+        /// ```
+        pub fn len(&self) -> usize {
+            acquire_broadcast(&self.internal).pending_for(self.next)
+        }
+        /// Returns whether there is no object available for this receiver
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+        /// Tries receiving the next object without waiting.
+        /// It returns `BroadcastTryRecvError::Lagged` if the receiver fell behind, the following receive returns the oldest available object.
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, mut r) = kanal::broadcast(2);
+        /// assert_eq!(r.try_recv(), Err(kanal::BroadcastTryRecvError::Empty));
+        /// for i in 0..3 {
+        ///     s.send(i)?;
+        /// }
+        /// assert_eq!(r.try_recv(), Err(kanal::BroadcastTryRecvError::Lagged(1)));
+        /// assert_eq!(r.try_recv()?, 1);
+        /// # anyhow::Ok(())
+        /// ```
+        pub fn try_recv(&mut self) -> Result<T, BroadcastTryRecvError>
+        where
+            T: Clone,
+        {
+            acquire_broadcast(&self.internal).next_for(&mut self.next)
+        }
+        /// Returns whether the receive side is terminated, and will not return any object in future receive calls
+        pub fn is_terminated(&self) -> bool {
+            let internal = acquire_broadcast(&self.internal);
+            internal.send_count == 0 && internal.pending_for(self.next) == 0
+        }
+    };
+}
+
+/// Sending side of a broadcast channel, every receiver gets a clone of every sent object.
+/// Sending never blocks, when the ring buffer is full the oldest object is overwritten,
+///  and receivers that did not receive it yet get a `Lagged` error.
+/// The sender works in both sync and async contexts.
+/// # Examples
+///
+/// ```
+/// let (s, mut r) = kanal::broadcast(16);
+/// let mut r2 = r.clone();
+/// s.send("hello")?;
+/// assert_eq!(r.recv()?, "hello");
+/// assert_eq!(r2.recv()?, "hello");
+/// # anyhow::Ok(())
+/// ```
+pub struct BroadcastSender<T> {
+    internal: BroadcastShared<T>,
+}
+
+impl<T> BroadcastSender<T> {
+    /// Sends the object to every receiver, the object is handed back inside the error if there is no receiver
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = kanal::broadcast(16);
+    /// drop(r);
+    /// assert_eq!(s.send(1), Err(kanal::SendError::ReceiveClosed(1)));
+    /// ```
+    pub fn send(&self, data: T) -> Result<(), SendError<T>> {
+        let mut internal = acquire_broadcast(&self.internal);
+        if internal.recv_count == 0 {
+            let send_count = internal.send_count;
+            drop(internal);
+            if send_count == 0 {
+                return Err(SendError::Closed(data));
+            }
+            return Err(SendError::ReceiveClosed(data));
+        }
+        let mut overwritten = None;
+        if internal.buffer.len() == internal.capacity {
+            overwritten = internal.buffer.pop_front();
+            internal.head += 1;
+        }
+        internal.buffer.push_back(data);
+        // async waiters are finished under the lock, so their futures never wait for the notification on drop
+        let mut handoffs: Vec<Handoff<()>> = Vec::with_capacity(internal.waiters.len());
+        while let Some(sig) = internal.waiters.pop_front() {
+            // Safety: it's safe to send to owned signal once
+            handoffs.push(unsafe { sig.send_locked(()) });
+        }
+        drop(internal);
+        // drop the overwritten object outside of the lock
+        drop(overwritten);
+        for handoff in handoffs {
+            handoff.finish();
+        }
+        Ok(())
+    }
+    /// Returns a new sync receiver that receives objects that are sent from now on
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = kanal::broadcast(16);
+    /// s.send(1)?;
+    /// let mut late = s.subscribe();
+    /// s.send(2)?;
+    /// assert_eq!(late.recv()?, 2);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn subscribe(&self) -> BroadcastReceiver<T> {
+        let mut internal = acquire_broadcast(&self.internal);
+        if internal.send_count > 0 {
+            internal.recv_count += 1;
+        }
+        BroadcastReceiver {
+            internal: self.internal.clone(),
+            next: internal.tail(),
+        }
+    }
+    /// Returns a new async receiver that receives objects that are sent from now on
+    #[cfg(feature = "async")]
+    pub fn subscribe_async(&self) -> BroadcastAsyncReceiver<T> {
+        self.subscribe().into_async()
+    }
+    /// Returns whether all receivers are dropped or not
+    pub fn is_disconnected(&self) -> bool {
+        acquire_broadcast(&self.internal).recv_count == 0
+    }
+    broadcast_shared_impl!();
+}
+
+impl<T> Clone for BroadcastSender<T> {
+    fn clone(&self) -> Self {
+        let mut internal = acquire_broadcast(&self.internal);
+        if internal.send_count > 0 {
+            internal.send_count += 1;
+        }
+        Self {
+            internal: self.internal.clone(),
+        }
+    }
+}
+
+impl<T> Drop for BroadcastSender<T> {
+    fn drop(&mut self) {
+        let mut internal = acquire_broadcast(&self.internal);
+        if internal.send_count > 0 {
+            internal.send_count -= 1;
+            if internal.send_count == 0 {
+                internal.terminate_waiters();
+            }
+        }
+    }
+}
+
+impl<T> Debug for BroadcastSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BroadcastSender {{ .. }}")
+    }
+}
+
+/// Receiving side of a broadcast channel in sync mode.
+/// Cloned receivers continue from the same position as the original one.
+pub struct BroadcastReceiver<T> {
+    internal: BroadcastShared<T>,
+    /// Position of the next object of this receiver in the stream of sent objects
+    next: u64,
+}
+
+impl<T: Clone> BroadcastReceiver<T> {
+    /// Receives the next object, waits if there is no new object.
+    /// It returns `BroadcastReceiveError::Lagged` if the receiver fell behind, the following receive returns the oldest available object.
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::thread::spawn;
+    /// let (s, mut r) = kanal::broadcast(16);
+    /// let mut r2 = r.clone();
+    /// let t = spawn(move || r2.recv());
+    /// s.send(1)?;
+    /// assert_eq!(r.recv()?, 1);
+    /// assert_eq!(t.join().unwrap()?, 1);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn recv(&mut self) -> Result<T, BroadcastReceiveError> {
+        loop {
+            let mut internal = acquire_broadcast(&self.internal);
+            match internal.next_for(&mut self.next) {
+                Ok(v) => return Ok(v),
+                Err(BroadcastTryRecvError::Empty) => {}
+                Err(err) => return Err(into_receive_error(err)),
+            }
+            let sig = SyncSignal::<()>::new(KanalPtr::default());
+            let _sig_address_holder = &sig;
+            internal.push_waiter(sig.as_signal());
+            drop(internal);
+            // the result does not matter, the channel state is checked again after the wake up
+            let _ = sig.wait();
+        }
+    }
+}
+
+impl<T> BroadcastReceiver<T> {
+    broadcast_recv_impl!();
+    /// Clones the receiver as the async version of it, the clone continues from the same position
+    #[cfg(feature = "async")]
+    pub fn clone_async(&self) -> BroadcastAsyncReceiver<T> {
+        self.clone().into_async()
+    }
+    /// Converts the receiver to the async version of it
+    #[cfg(feature = "async")]
+    pub(crate) fn into_async(self) -> BroadcastAsyncReceiver<T> {
+        let this = std::mem::ManuallyDrop::new(self);
+        BroadcastAsyncReceiver {
+            // Safety: self is not going to be dropped, so the internal is moved out of it
+            internal: unsafe { std::ptr::read(&this.internal) },
+            next: this.next,
+        }
+    }
+    broadcast_shared_impl!();
+}
+
+impl<T> Iterator for BroadcastReceiver<T>
+where
+    T: Clone,
+{
+    type Item = Result<T, BroadcastReceiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.recv() {
+            Err(BroadcastReceiveError::SendClosed | BroadcastReceiveError::Closed) => None,
+            r => Some(r),
+        }
+    }
+}
+
+/// Receiving side of a broadcast channel in async mode.
+/// Cloned receivers continue from the same position as the original one.
+#[cfg(feature = "async")]
+pub struct BroadcastAsyncReceiver<T> {
+    internal: BroadcastShared<T>,
+    /// Position of the next object of this receiver in the stream of sent objects
+    next: u64,
+}
+
+#[cfg(feature = "async")]
+impl<T: Clone> BroadcastAsyncReceiver<T> {
+    /// Returns a future to receive the next object.
+    /// It resolves to `BroadcastReceiveError::Lagged` if the receiver fell behind, the following receive returns the oldest available object.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let (s, mut r) = kanal::broadcast_async(16);
+    /// let mut sync_receiver = r.clone_sync();
+    /// s.send(1)?;
+    /// assert_eq!(r.recv().await?, 1);
+    /// assert_eq!(sync_receiver.try_recv()?, 1);
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    pub fn recv(&mut self) -> BroadcastReceiveFuture<'_, T> {
+        BroadcastReceiveFuture {
+            receiver: self,
+            waiting: false,
+            sig: None,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> BroadcastAsyncReceiver<T> {
+    broadcast_recv_impl!();
+    /// Clones the receiver as the sync version of it, the clone continues from the same position
+    pub fn clone_sync(&self) -> BroadcastReceiver<T> {
+        let mut internal = acquire_broadcast(&self.internal);
+        if internal.recv_count > 0 {
+            internal.recv_count += 1;
+        }
+        BroadcastReceiver {
+            internal: self.internal.clone(),
+            next: self.next,
+        }
+    }
+    broadcast_shared_impl!();
+}
+
+impl<T> Clone for BroadcastReceiver<T> {
+    fn clone(&self) -> Self {
+        let mut internal = acquire_broadcast(&self.internal);
+        if internal.recv_count > 0 {
+            internal.recv_count += 1;
+        }
+        Self {
+            internal: self.internal.clone(),
+            next: self.next,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Clone for BroadcastAsyncReceiver<T> {
+    fn clone(&self) -> Self {
+        self.clone_sync().into_async()
+    }
+}
+
+/// Drops a receiver of the broadcast channel, the buffer is released when the last receiver is gone
+fn drop_receiver<T>(internal: &BroadcastShared<T>) {
+    let mut internal = acquire_broadcast(internal);
+    if internal.recv_count > 0 {
+        internal.recv_count -= 1;
+        if internal.recv_count == 0 {
+            let buffer = std::mem::take(&mut internal.buffer);
+            drop(internal);
+            // nobody is going to receive buffered objects, drop them outside of the lock
+            drop(buffer);
+        }
+    }
+}
+
+impl<T> Drop for BroadcastReceiver<T> {
+    fn drop(&mut self) {
+        drop_receiver(&self.internal);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Drop for BroadcastAsyncReceiver<T> {
+    fn drop(&mut self) {
+        drop_receiver(&self.internal);
+    }
+}
+
+impl<T> Debug for BroadcastReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BroadcastReceiver {{ .. }}")
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Debug for BroadcastAsyncReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BroadcastAsyncReceiver {{ .. }}")
+    }
+}
+
+/// Receive future of a broadcast channel, it must be polled to perform receive action
+#[cfg(feature = "async")]
+#[must_use = "futures do nothing unless you .await or poll them"]
+pub struct BroadcastReceiveFuture<'a, T> {
+    receiver: &'a mut BroadcastAsyncReceiver<T>,
+    waiting: bool,
+    /// The signal is boxed as its address is shared with the waiters of the channel, so the future can be moved freely
+    sig: Option<Box<AsyncSignal<()>>>,
+}
+
+#[cfg(feature = "async")]
+impl<'a, T: Clone> Future for BroadcastReceiveFuture<'a, T> {
+    type Output = Result<T, BroadcastReceiveError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut internal = acquire_broadcast(&this.receiver.internal);
+        if this.waiting {
+            let sig = this.sig.as_mut().unwrap();
+            if internal.waiter_exists(sig.as_signal()) {
+                // the signal is only read by senders under the lock, so it's safe to update the waker here
+                if !sig.will_wake(cx.waker()) {
+                    sig.register(cx.waker());
+                }
+                return Poll::Pending;
+            }
+            // the signal is finished by a sender under the lock, check the channel again
+            this.waiting = false;
+        }
+        match internal.next_for(&mut this.receiver.next) {
+            Ok(v) => return Poll::Ready(Ok(v)),
+            Err(BroadcastTryRecvError::Empty) => {}
+            Err(err) => return Poll::Ready(Err(into_receive_error(err))),
+        }
+        let sig = match &mut this.sig {
+            Some(sig) => {
+                **sig = AsyncSignal::new();
+                sig
+            }
+            None => this.sig.insert(Box::new(AsyncSignal::new())),
+        };
+        sig.register(cx.waker());
+        internal.push_waiter(sig.as_signal());
+        this.waiting = true;
+        Poll::Pending
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, T> Drop for BroadcastReceiveFuture<'a, T> {
+    fn drop(&mut self) {
+        if self.waiting {
+            let mut internal = acquire_broadcast(&self.receiver.internal);
+            // if the signal is not in the waiters anymore, a sender finished it under the lock,
+            //  and it only holds the waker now
+            internal.cancel_waiter(self.sig.as_ref().unwrap().as_signal());
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, T> Debug for BroadcastReceiveFuture<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BroadcastReceiveFuture {{ .. }}")
+    }
+}
+
+/// Returns sender and sync receiver of a broadcast channel with the capacity
+pub(crate) fn new_broadcast<T>(capacity: usize) -> (BroadcastSender<T>, BroadcastReceiver<T>) {
+    let internal = BroadcastInternal::new(capacity);
+    (
+        BroadcastSender {
+            internal: internal.clone(),
+        },
+        BroadcastReceiver { internal, next: 0 },
+    )
+}
@@ -0,0 +1,208 @@
+use std::fmt;
+
+use crate::internal::{ChannelInternal, Internal};
+use crate::queue::{PriorityQueue, Queue};
+use crate::signal::SPIN_COUNT;
+#[cfg(feature = "async")]
+use crate::{AsyncReceiver, AsyncSender};
+use crate::{OverflowPolicy, PriorityOrder, Receiver, Sender, UNBOUNDED_STARTING_SIZE};
+
+/// Builder of channels to configure options of the channel at construction,
+///  it can produce any combination of sync and async endpoints of the configured channel.
+/// # Examples
+///
+/// ```
+/// use kanal::{Builder, OverflowPolicy};
+///
+/// let (s, r) = Builder::new()
+///     .bounded(64)
+///     .overflow(OverflowPolicy::DropOldest)
+///     .name("frames")
+///     .metrics(true)
+///     .build();
+/// s.send(1)?;
+/// assert_eq!(r.recv()?, 1);
+/// assert_eq!(format!("{:?}", s), r#"Sender { name: "frames", .. }"#);
+/// assert_eq!(r.metrics().unwrap().sent, 1);
+/// # anyhow::Ok(())
+/// ```
+pub struct Builder<T> {
+    capacity: Option<usize>,
+    overflow: OverflowPolicy,
+    name: Option<String>,
+    spins: usize,
+    preallocate: Option<usize>,
+    metrics: bool,
+    priority: Option<PriorityOrder<T>>,
+}
+
+impl<T> Builder<T> {
+    /// Returns a builder of an unbounded FIFO channel with default options
+    pub fn new() -> Self {
+        Self {
+            capacity: None,
+            overflow: OverflowPolicy::Block,
+            name: None,
+            spins: SPIN_COUNT,
+            preallocate: None,
+            metrics: false,
+            priority: None,
+        }
+    }
+    /// Sets capacity of the channel, see [`bounded`](crate::bounded)
+    pub fn bounded(mut self, size: usize) -> Self {
+        self.capacity = Some(size);
+        self
+    }
+    /// Removes the capacity limit of the channel, see [`unbounded`](crate::unbounded)
+    pub fn unbounded(mut self) -> Self {
+        self.capacity = None;
+        self
+    }
+    /// Sets behavior of the bounded channel when the queue is full, see [`OverflowPolicy`]
+    /// # Panics
+    /// Building panics if the policy is not `Block` and the capacity is zero, as every object would be evicted.
+    pub fn overflow(mut self, overflow: OverflowPolicy) -> Self {
+        self.overflow = overflow;
+        self
+    }
+    /// Sets debug name of the channel, it's shown in the `Debug` output of the endpoints
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+    /// Sets count of spins of sync senders and receivers before they wait on the os for a peer,
+    ///  more spins lower the latency of busy channels at the cost of cpu time, and zero disables spinning
+    pub fn spins(mut self, spins: usize) -> Self {
+        self.spins = spins;
+        self
+    }
+    /// Sets count of objects that the channel queue allocates room for on construction,
+    ///  it defaults to the capacity of bounded channels
+    pub fn preallocate(mut self, size: usize) -> Self {
+        self.preallocate = Some(size);
+        self
+    }
+    /// Enables collection of channel metrics, see [`ChannelMetrics`]
+    pub fn metrics(mut self, enabled: bool) -> Self {
+        self.metrics = enabled;
+        self
+    }
+    /// Buffers objects of the channel in a priority queue with the ordering, see [`PriorityOrder`]
+    pub fn priority(mut self, order: PriorityOrder<T>) -> Self {
+        self.priority = Some(order);
+        self
+    }
+    /// Returns internal of the configured channel
+    fn internal(self) -> Internal<T> {
+        assert!(
+            self.overflow == OverflowPolicy::Block || self.capacity != Some(0),
+            "overflow policy of a zero sized channel must be Block"
+        );
+        let capacity = self.capacity.unwrap_or(0);
+        let preallocate = self
+            .preallocate
+            .unwrap_or(self.capacity.unwrap_or(UNBOUNDED_STARTING_SIZE));
+        let queue = match self.priority {
+            Some(order) => Queue::Priority(PriorityQueue::new(order, preallocate)),
+            None => Queue::fifo(preallocate),
+        };
+        let mut internal = ChannelInternal::with_queue(self.capacity.is_some(), capacity, queue);
+        internal.overflow = self.overflow;
+        internal.name = self.name.map(String::into_boxed_str);
+        internal.spins = self.spins;
+        if self.metrics {
+            internal.metrics = Some(ChannelMetrics::default());
+        }
+        internal.into_shared()
+    }
+    /// Returns sync sender and sync receiver of the configured channel
+    pub fn build(self) -> (Sender<T>, Receiver<T>) {
+        let internal = self.internal();
+        (Sender::new(internal.clone()), Receiver::new(internal))
+    }
+    /// Returns async sender and async receiver of the configured channel
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let (s, r) = kanal::Builder::new().bounded(1).build_async();
+    /// s.send(1).await?;
+    /// assert_eq!(r.recv().await?, 1);
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn build_async(self) -> (AsyncSender<T>, AsyncReceiver<T>) {
+        let internal = self.internal();
+        (
+            AsyncSender::new(internal.clone()),
+            AsyncReceiver::new(internal),
+        )
+    }
+    /// Returns sync sender and async receiver of the configured channel
+    #[cfg(feature = "async")]
+    pub fn build_sync_async(self) -> (Sender<T>, AsyncReceiver<T>) {
+        let internal = self.internal();
+        (Sender::new(internal.clone()), AsyncReceiver::new(internal))
+    }
+    /// Returns async sender and sync receiver of the configured channel
+    #[cfg(feature = "async")]
+    pub fn build_async_sync(self) -> (AsyncSender<T>, Receiver<T>) {
+        let internal = self.internal();
+        (AsyncSender::new(internal.clone()), Receiver::new(internal))
+    }
+}
+
+impl<T> Default for Builder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for Builder<T> {
+    fn clone(&self) -> Self {
+        Self {
+            capacity: self.capacity,
+            overflow: self.overflow,
+            name: self.name.clone(),
+            spins: self.spins,
+            preallocate: self.preallocate,
+            metrics: self.metrics,
+            priority: self.priority,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Builder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("capacity", &self.capacity)
+            .field("overflow", &self.overflow)
+            .field("name", &self.name)
+            .field("spins", &self.spins)
+            .field("preallocate", &self.preallocate)
+            .field("metrics", &self.metrics)
+            .field("priority", &self.priority)
+            .finish()
+    }
+}
+
+/// Snapshot of the metrics of a channel, metrics are only collected for channels
+///  that enable them with [`Builder::metrics`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ChannelMetrics {
+    /// Count of objects that are accepted by the channel, including the evicted ones
+    pub sent: u64,
+    /// Count of objects that are evicted based on the overflow policy
+    pub evicted: u64,
+    /// Count of buffered objects
+    pub len: usize,
+    /// The highest count of buffered objects since the channel was created
+    pub peak_len: usize,
+    /// Count of senders that are waiting on the waitlist
+    pub waiting_senders: usize,
+    /// Count of receivers that are waiting on the waitlist
+    pub waiting_receivers: usize,
+}
@@ -1,45 +1,280 @@
 #![forbid(unsafe_code)]
 use std::fmt;
 use std::fmt::Debug;
-/// Error type for channel send operations without timeout
-#[derive(Debug, PartialEq, Eq)]
-pub enum SendError {
+/// Error type for channel send operations without timeout, it hands the unsent object back to the caller
+#[derive(PartialEq, Eq)]
+pub enum SendError<T> {
     /// Indicates that the channel is closed on both sides with a call to `close()`
-    Closed,
+    Closed(T),
     /// Indicates that all receiver instances are dropped and the channel is closed from the receive side
-    ReceiveClosed,
+    ReceiveClosed(T),
+}
+impl<T> SendError<T> {
+    /// Returns the object that failed to be sent
+    pub fn into_inner(self) -> T {
+        match self {
+            SendError::Closed(v) | SendError::ReceiveClosed(v) => v,
+        }
+    }
+    /// Maps the object inside the error with the provided function and keeps the variant
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> SendError<U> {
+        match self {
+            SendError::Closed(v) => SendError::Closed(f(v)),
+            SendError::ReceiveClosed(v) => SendError::ReceiveClosed(f(v)),
+        }
+    }
+    /// Returns whether the channel is closed on both sides or not
+    pub fn is_closed(&self) -> bool {
+        matches!(self, SendError::Closed(_))
+    }
+    /// Returns whether the receive side of the channel is closed or not, it's true for both closed variants
+    pub fn is_disconnected(&self) -> bool {
+        true
+    }
+    /// Returns whether the operation failed because of the channel capacity, it's always false for blocking sends
+    pub fn is_full(&self) -> bool {
+        false
+    }
+    /// Returns whether the operation reached its timeout, it's always false for operations without timeout
+    pub fn is_timeout(&self) -> bool {
+        false
+    }
 }
-impl std::error::Error for SendError {}
-impl fmt::Display for SendError {
+impl<T> Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SendError::Closed(_) => write!(f, "Closed(..)"),
+            SendError::ReceiveClosed(_) => write!(f, "ReceiveClosed(..)"),
+        }
+    }
+}
+impl<T> std::error::Error for SendError<T> {}
+impl<T> fmt::Display for SendError<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(
             match *self {
-                SendError::Closed => "send to a closed channel",
-                SendError::ReceiveClosed => "send to a half closed channel",
+                SendError::Closed(_) => "send to a closed channel",
+                SendError::ReceiveClosed(_) => "send to a half closed channel",
             },
             f,
         )
     }
 }
 
-/// Error type for channel send operations with timeout
-#[derive(Debug, PartialEq, Eq)]
-pub enum SendErrorTimeout {
+/// Error type for channel send operations with timeout, it hands the unsent object back to the caller
+#[derive(PartialEq, Eq)]
+pub enum SendErrorTimeout<T> {
     /// Indicates that the channel is closed on both sides with a call to `close()`
-    Closed,
+    Closed(T),
     /// Indicates that all receiver instances are dropped and the channel is closed from the receive side
-    ReceiveClosed,
+    ReceiveClosed(T),
     /// Indicates that channel operation reached timeout and is canceled
-    Timeout,
+    Timeout(T),
+}
+impl<T> SendErrorTimeout<T> {
+    /// Returns the object that failed to be sent
+    pub fn into_inner(self) -> T {
+        match self {
+            SendErrorTimeout::Closed(v)
+            | SendErrorTimeout::ReceiveClosed(v)
+            | SendErrorTimeout::Timeout(v) => v,
+        }
+    }
+    /// Returns whether the channel is closed on both sides or not
+    pub fn is_closed(&self) -> bool {
+        matches!(self, SendErrorTimeout::Closed(_))
+    }
+    /// Returns whether the receive side of the channel is closed or not
+    pub fn is_disconnected(&self) -> bool {
+        matches!(
+            self,
+            SendErrorTimeout::Closed(_) | SendErrorTimeout::ReceiveClosed(_)
+        )
+    }
+    /// Returns whether the operation failed because of the channel capacity, it's always false for blocking sends
+    pub fn is_full(&self) -> bool {
+        false
+    }
+    /// Returns whether the operation reached its timeout
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, SendErrorTimeout::Timeout(_))
+    }
+}
+impl<T> From<SendError<T>> for SendErrorTimeout<T> {
+    fn from(err: SendError<T>) -> Self {
+        match err {
+            SendError::Closed(v) => SendErrorTimeout::Closed(v),
+            SendError::ReceiveClosed(v) => SendErrorTimeout::ReceiveClosed(v),
+        }
+    }
+}
+impl<T> Debug for SendErrorTimeout<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SendErrorTimeout::Closed(_) => write!(f, "Closed(..)"),
+            SendErrorTimeout::ReceiveClosed(_) => write!(f, "ReceiveClosed(..)"),
+            SendErrorTimeout::Timeout(_) => write!(f, "Timeout(..)"),
+        }
+    }
+}
+impl<T> std::error::Error for SendErrorTimeout<T> {}
+impl<T> fmt::Display for SendErrorTimeout<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(
+            match *self {
+                SendErrorTimeout::Closed(_) => "send to a closed channel",
+                SendErrorTimeout::ReceiveClosed(_) => "send to a half closed channel",
+                SendErrorTimeout::Timeout(_) => "send timeout",
+            },
+            f,
+        )
+    }
+}
+
+/// Error type for channel operations that send several objects, it hands the count of sent objects and the object
+///  that failed to be sent back to the caller, objects that are not taken from the iterator yet stay in it
+#[derive(PartialEq, Eq)]
+pub enum SendManyError<T> {
+    /// Indicates that the channel is closed on both sides with a call to `close()`
+    Closed(usize, Option<T>),
+    /// Indicates that all receiver instances are dropped and the channel is closed from the receive side
+    ReceiveClosed(usize, Option<T>),
+}
+impl<T> SendManyError<T> {
+    /// Returns the count of objects that are sent before the channel got closed
+    pub fn sent(&self) -> usize {
+        match *self {
+            SendManyError::Closed(n, _) | SendManyError::ReceiveClosed(n, _) => n,
+        }
+    }
+    /// Returns the object that is taken from the iterator and failed to be sent, if there is any
+    pub fn into_inner(self) -> Option<T> {
+        match self {
+            SendManyError::Closed(_, v) | SendManyError::ReceiveClosed(_, v) => v,
+        }
+    }
+    /// Adds the count of objects that are sent before the operation that returned the error, and the object that failed to be sent
+    pub(crate) fn after(self, sent: usize, data: T) -> Self {
+        match self {
+            SendManyError::Closed(n, _) => SendManyError::Closed(sent + n, Some(data)),
+            SendManyError::ReceiveClosed(n, _) => {
+                SendManyError::ReceiveClosed(sent + n, Some(data))
+            }
+        }
+    }
+    /// Returns whether the channel is closed on both sides or not
+    pub fn is_closed(&self) -> bool {
+        matches!(self, SendManyError::Closed(..))
+    }
+    /// Returns whether the receive side of the channel is closed or not, it's true for both closed variants
+    pub fn is_disconnected(&self) -> bool {
+        true
+    }
+}
+impl<T> SendError<T> {
+    /// Converts the error of a send operation that is part of sending several objects, with the count of objects that are sent before it
+    pub(crate) fn into_many(self, sent: usize) -> SendManyError<T> {
+        match self {
+            SendError::Closed(v) => SendManyError::Closed(sent, Some(v)),
+            SendError::ReceiveClosed(v) => SendManyError::ReceiveClosed(sent, Some(v)),
+        }
+    }
+}
+impl<T> Debug for SendManyError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SendManyError::Closed(n, _) => write!(f, "Closed({}, ..)", n),
+            SendManyError::ReceiveClosed(n, _) => write!(f, "ReceiveClosed({}, ..)", n),
+        }
+    }
+}
+impl<T> std::error::Error for SendManyError<T> {}
+impl<T> fmt::Display for SendManyError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SendManyError::Closed(n, _) => {
+                write!(f, "send to a closed channel after {} objects", n)
+            }
+            SendManyError::ReceiveClosed(n, _) => {
+                write!(f, "send to a half closed channel after {} objects", n)
+            }
+        }
+    }
+}
+
+/// Error type for channel try send operations, it hands the unsent object back to the caller
+#[derive(PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// Indicates that the channel is full and there is no receiver waiting for the object
+    Full(T),
+    /// Indicates that the channel internal lock is held by another operation, it's only returned by realtime operations
+    Contended(T),
+    /// Indicates that the channel is closed on both sides with a call to `close()`
+    Closed(T),
+    /// Indicates that all receiver instances are dropped and the channel is closed from the receive side
+    ReceiveClosed(T),
+}
+impl<T> TrySendError<T> {
+    /// Returns the object that failed to be sent
+    pub fn into_inner(self) -> T {
+        match self {
+            TrySendError::Full(v)
+            | TrySendError::Contended(v)
+            | TrySendError::Closed(v)
+            | TrySendError::ReceiveClosed(v) => v,
+        }
+    }
+    /// Returns whether the channel is closed on both sides or not
+    pub fn is_closed(&self) -> bool {
+        matches!(self, TrySendError::Closed(_))
+    }
+    /// Returns whether the receive side of the channel is closed or not
+    pub fn is_disconnected(&self) -> bool {
+        matches!(
+            self,
+            TrySendError::Closed(_) | TrySendError::ReceiveClosed(_)
+        )
+    }
+    /// Returns whether the channel was full or not
+    pub fn is_full(&self) -> bool {
+        matches!(self, TrySendError::Full(_))
+    }
+    /// Returns whether the channel internal lock was contended or not
+    pub fn is_contended(&self) -> bool {
+        matches!(self, TrySendError::Contended(_))
+    }
+    /// Returns whether the operation reached its timeout, it's always false for try operations
+    pub fn is_timeout(&self) -> bool {
+        false
+    }
+}
+impl<T> From<SendError<T>> for TrySendError<T> {
+    fn from(err: SendError<T>) -> Self {
+        match err {
+            SendError::Closed(v) => TrySendError::Closed(v),
+            SendError::ReceiveClosed(v) => TrySendError::ReceiveClosed(v),
+        }
+    }
+}
+impl<T> Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TrySendError::Full(_) => write!(f, "Full(..)"),
+            TrySendError::Contended(_) => write!(f, "Contended(..)"),
+            TrySendError::Closed(_) => write!(f, "Closed(..)"),
+            TrySendError::ReceiveClosed(_) => write!(f, "ReceiveClosed(..)"),
+        }
+    }
 }
-impl std::error::Error for SendErrorTimeout {}
-impl fmt::Display for SendErrorTimeout {
+impl<T> std::error::Error for TrySendError<T> {}
+impl<T> fmt::Display for TrySendError<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(
             match *self {
-                SendErrorTimeout::Closed => "send to a closed channel",
-                SendErrorTimeout::ReceiveClosed => "send to a half closed channel",
-                SendErrorTimeout::Timeout => "send timeout",
+                TrySendError::Full(_) => "send to a full channel",
+                TrySendError::Contended(_) => "send to a contended channel",
+                TrySendError::Closed(_) => "send to a closed channel",
+                TrySendError::ReceiveClosed(_) => "send to a half closed channel",
             },
             f,
         )
@@ -54,6 +289,24 @@ pub enum ReceiveError {
     /// Indicates that all sender instances are dropped and the channel is closed from the send side
     SendClosed,
 }
+impl ReceiveError {
+    /// Returns whether the channel is closed on both sides or not
+    pub fn is_closed(&self) -> bool {
+        matches!(self, ReceiveError::Closed)
+    }
+    /// Returns whether the send side of the channel is closed or not, it's true for both closed variants
+    pub fn is_disconnected(&self) -> bool {
+        true
+    }
+    /// Returns whether the operation failed because the channel was empty, it's always false for blocking receives
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    /// Returns whether the operation reached its timeout, it's always false for operations without timeout
+    pub fn is_timeout(&self) -> bool {
+        false
+    }
+}
 impl std::error::Error for ReceiveError {}
 impl fmt::Display for ReceiveError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -77,6 +330,35 @@ pub enum ReceiveErrorTimeout {
     /// Indicates that channel operation reached timeout and is canceled
     Timeout,
 }
+impl ReceiveErrorTimeout {
+    /// Returns whether the channel is closed on both sides or not
+    pub fn is_closed(&self) -> bool {
+        matches!(self, ReceiveErrorTimeout::Closed)
+    }
+    /// Returns whether the send side of the channel is closed or not
+    pub fn is_disconnected(&self) -> bool {
+        matches!(
+            self,
+            ReceiveErrorTimeout::Closed | ReceiveErrorTimeout::SendClosed
+        )
+    }
+    /// Returns whether the operation failed because the channel was empty, it's always false for blocking receives
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    /// Returns whether the operation reached its timeout
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, ReceiveErrorTimeout::Timeout)
+    }
+}
+impl From<ReceiveError> for ReceiveErrorTimeout {
+    fn from(err: ReceiveError) -> Self {
+        match err {
+            ReceiveError::Closed => ReceiveErrorTimeout::Closed,
+            ReceiveError::SendClosed => ReceiveErrorTimeout::SendClosed,
+        }
+    }
+}
 impl std::error::Error for ReceiveErrorTimeout {}
 impl fmt::Display for ReceiveErrorTimeout {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -90,3 +372,174 @@ impl fmt::Display for ReceiveErrorTimeout {
         )
     }
 }
+
+/// Error type for channel try receive operations
+#[derive(Debug, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// Indicates that the channel is empty and there is no sender waiting to hand an object
+    Empty,
+    /// Indicates that the channel internal lock is held by another operation, it's only returned by realtime operations
+    Contended,
+    /// Indicates that the channel is closed on both sides with a call to `close()`
+    Closed,
+    /// Indicates that all sender instances are dropped and the channel is closed from the send side
+    SendClosed,
+}
+impl TryRecvError {
+    /// Returns whether the channel is closed on both sides or not
+    pub fn is_closed(&self) -> bool {
+        matches!(self, TryRecvError::Closed)
+    }
+    /// Returns whether the send side of the channel is closed or not
+    pub fn is_disconnected(&self) -> bool {
+        matches!(self, TryRecvError::Closed | TryRecvError::SendClosed)
+    }
+    /// Returns whether the channel was empty or not
+    pub fn is_empty(&self) -> bool {
+        matches!(self, TryRecvError::Empty)
+    }
+    /// Returns whether the channel internal lock was contended or not
+    pub fn is_contended(&self) -> bool {
+        matches!(self, TryRecvError::Contended)
+    }
+    /// Returns whether the operation reached its timeout, it's always false for try operations
+    pub fn is_timeout(&self) -> bool {
+        false
+    }
+}
+impl From<ReceiveError> for TryRecvError {
+    fn from(err: ReceiveError) -> Self {
+        match err {
+            ReceiveError::Closed => TryRecvError::Closed,
+            ReceiveError::SendClosed => TryRecvError::SendClosed,
+        }
+    }
+}
+impl std::error::Error for TryRecvError {}
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(
+            match *self {
+                TryRecvError::Empty => "receive from an empty channel",
+                TryRecvError::Contended => "receive from a contended channel",
+                TryRecvError::Closed => "receive from a closed channel",
+                TryRecvError::SendClosed => "receive from a half closed channel",
+            },
+            f,
+        )
+    }
+}
+
+/// Error type for broadcast channel receive operations
+#[derive(Debug, PartialEq, Eq)]
+pub enum BroadcastReceiveError {
+    /// Indicates that the receiver fell behind and the given count of objects were overwritten before it received them,
+    ///  the next receive returns the oldest object that is still in the channel
+    Lagged(u64),
+    /// Indicates that the channel is closed on both sides with a call to `close()`
+    Closed,
+    /// Indicates that all sender instances are dropped and the receiver received every remaining object
+    SendClosed,
+}
+impl BroadcastReceiveError {
+    /// Returns whether the channel is closed on both sides or not
+    pub fn is_closed(&self) -> bool {
+        matches!(self, BroadcastReceiveError::Closed)
+    }
+    /// Returns whether the send side of the channel is closed or not
+    pub fn is_disconnected(&self) -> bool {
+        matches!(
+            self,
+            BroadcastReceiveError::Closed | BroadcastReceiveError::SendClosed
+        )
+    }
+    /// Returns whether the receiver missed objects or not
+    pub fn is_lagged(&self) -> bool {
+        matches!(self, BroadcastReceiveError::Lagged(_))
+    }
+    /// Returns whether the operation failed because the channel was empty, it's always false for blocking receives
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    /// Returns whether the operation reached its timeout, it's always false for operations without timeout
+    pub fn is_timeout(&self) -> bool {
+        false
+    }
+}
+impl std::error::Error for BroadcastReceiveError {}
+impl fmt::Display for BroadcastReceiveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BroadcastReceiveError::Lagged(n) => {
+                write!(f, "receiver lagged behind by {} messages", n)
+            }
+            BroadcastReceiveError::Closed => fmt::Display::fmt("receive from a closed channel", f),
+            BroadcastReceiveError::SendClosed => {
+                fmt::Display::fmt("receive from a half closed channel", f)
+            }
+        }
+    }
+}
+
+/// Error type for broadcast channel try receive operations
+#[derive(Debug, PartialEq, Eq)]
+pub enum BroadcastTryRecvError {
+    /// Indicates that there is no new object for the receiver
+    Empty,
+    /// Indicates that the receiver fell behind and the given count of objects were overwritten before it received them,
+    ///  the next receive returns the oldest object that is still in the channel
+    Lagged(u64),
+    /// Indicates that the channel is closed on both sides with a call to `close()`
+    Closed,
+    /// Indicates that all sender instances are dropped and the receiver received every remaining object
+    SendClosed,
+}
+impl BroadcastTryRecvError {
+    /// Returns whether the channel is closed on both sides or not
+    pub fn is_closed(&self) -> bool {
+        matches!(self, BroadcastTryRecvError::Closed)
+    }
+    /// Returns whether the send side of the channel is closed or not
+    pub fn is_disconnected(&self) -> bool {
+        matches!(
+            self,
+            BroadcastTryRecvError::Closed | BroadcastTryRecvError::SendClosed
+        )
+    }
+    /// Returns whether the receiver missed objects or not
+    pub fn is_lagged(&self) -> bool {
+        matches!(self, BroadcastTryRecvError::Lagged(_))
+    }
+    /// Returns whether there was no new object for the receiver or not
+    pub fn is_empty(&self) -> bool {
+        matches!(self, BroadcastTryRecvError::Empty)
+    }
+    /// Returns whether the operation reached its timeout, it's always false for try operations
+    pub fn is_timeout(&self) -> bool {
+        false
+    }
+}
+impl From<BroadcastReceiveError> for BroadcastTryRecvError {
+    fn from(err: BroadcastReceiveError) -> Self {
+        match err {
+            BroadcastReceiveError::Lagged(n) => BroadcastTryRecvError::Lagged(n),
+            BroadcastReceiveError::Closed => BroadcastTryRecvError::Closed,
+            BroadcastReceiveError::SendClosed => BroadcastTryRecvError::SendClosed,
+        }
+    }
+}
+impl std::error::Error for BroadcastTryRecvError {}
+impl fmt::Display for BroadcastTryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BroadcastTryRecvError::Empty => fmt::Display::fmt("receive from an empty channel", f),
+            BroadcastTryRecvError::Lagged(n) => {
+                write!(f, "receiver lagged behind by {} messages", n)
+            }
+            BroadcastTryRecvError::Closed => fmt::Display::fmt("receive from a closed channel", f),
+            BroadcastTryRecvError::SendClosed => {
+                fmt::Display::fmt("receive from a half closed channel", f)
+            }
+        }
+    }
+}
@@ -1,16 +1,23 @@
 use futures_core::{FusedStream, Future, Stream};
+use futures_sink::Sink;
 use std::{
     fmt::Debug,
-    mem::{needs_drop, size_of, MaybeUninit},
+    mem::{needs_drop, size_of, ManuallyDrop, MaybeUninit},
     pin::Pin,
-    task::Poll,
+    task::{Poll, RawWaker, RawWakerVTable, Waker},
+    time::Instant,
 };
 
 use crate::{
-    internal::{acquire_internal, Internal},
+    backoff,
+    internal::{acquire_internal, terminated_recv_error, Internal},
+    mutex::Mutex,
     pointer::KanalPtr,
-    signal::AsyncSignal,
-    state, AsyncReceiver, ReceiveError, SendError,
+    signal::{AsyncSignal, SelectEntry, SelectSignal},
+    state,
+    timer::Delay,
+    AsyncReceiver, AsyncSender, ReceiveError, ReceiveErrorTimeout, SendError, SendErrorTimeout,
+    TryRecvError, TrySendError,
 };
 
 use pin_project_lite::pin_project;
@@ -34,40 +41,51 @@ impl FutureState {
     }
 }
 
-pin_project! {
-    /// Send future to send an object to the channel asynchronously
-    /// It must be polled to perform send action
-    #[must_use = "futures do nothing unless you .await or poll them"]
-    pub struct SendFuture<'a, T> {
-        pub(crate) state: FutureState,
-        pub(crate) internal: &'a Internal<T>,
-        #[pin]
-        pub(crate) sig: AsyncSignal<T>,
-        pub(crate) data: MaybeUninit<T>,
-    }
-    impl<'a,T> PinnedDrop for SendFuture<'a,T> {
-        fn drop(mut this: Pin<&mut Self>) {
-            if !this.state.is_done() {
-                if this.state.is_waiting() && !acquire_internal(this.internal).cancel_send_signal(this.sig.as_signal()) {
-                    // a receiver got signal ownership, should wait until the response
-                    if this.sig.wait_indefinitely() == state::UNLOCKED {
-                        // no need to drop data is moved to receiver
-                        return
-                    }
-                }
-                // signal is canceled, or in zero stated, drop data locally
-                if needs_drop::<T>(){
-                    // Safety: data is not moved it's safe to drop it
-                    unsafe {
-                        this.drop_local_data();
-                    }
-                }
+/// State machine of an asynchronous send operation, it's shared between send futures and the sink.
+/// The core must stay in the same memory location while it's waiting, as its signal is registered in the channel waitlist.
+pub(crate) struct SendCore<T> {
+    state: FutureState,
+    sig: AsyncSignal<T>,
+    data: MaybeUninit<T>,
+}
+
+impl<T> SendCore<T> {
+    #[inline(always)]
+    pub(crate) fn new(data: T) -> Self {
+        if size_of::<T>() > size_of::<*mut T>() {
+            SendCore {
+                state: FutureState::Zero,
+                sig: AsyncSignal::new(),
+                data: MaybeUninit::new(data),
+            }
+        } else {
+            SendCore {
+                state: FutureState::Zero,
+                sig: AsyncSignal::new_inside_ptr(KanalPtr::new_owned(data)),
+                data: MaybeUninit::uninit(),
             }
         }
     }
-}
-
-impl<'a, T> SendFuture<'a, T> {
+    /// Returns a core without data that is already finished, it's used as a placeholder to reuse the memory of the core.
+    #[inline(always)]
+    pub(crate) fn done() -> Self {
+        SendCore {
+            state: FutureState::Done,
+            sig: AsyncSignal::new(),
+            data: MaybeUninit::uninit(),
+        }
+    }
+    /// Returns the core of a pinned future, methods of the core never move it
+    #[inline(always)]
+    pub(crate) fn get(self: Pin<&mut Self>) -> &mut Self {
+        // Safety: the core is only accessed through references, and it's not moved out of its pin
+        unsafe { self.get_unchecked_mut() }
+    }
+    /// Returns whether the core finished its operation or not
+    #[inline(always)]
+    pub(crate) fn is_done(&self) -> bool {
+        self.state.is_done()
+    }
     /// # Safety
     /// it's only safe to call this function once and only if send operation will finish after this call.
     #[inline(always)]
@@ -79,114 +97,135 @@ impl<'a, T> SendFuture<'a, T> {
             self.sig.read_kanal_ptr()
         }
     }
-    /// # Safety
-    /// it's only safe to call this function once and only if send operation fails
-    #[inline(always)]
-    unsafe fn drop_local_data(&mut self) {
-        if size_of::<T>() > size_of::<*mut T>() {
-            self.data.assume_init_drop();
-        } else {
-            self.sig.read_and_drop_ptr();
+    /// Withdraws the signal of a waiting core from the channel waitlist after its deadline is reached.
+    /// If a receiver already took the signal, it returns the result of the handoff instead.
+    pub(crate) fn cancel_timeout(
+        &mut self,
+        internal: &Internal<T>,
+    ) -> Result<(), SendErrorTimeout<T>> {
+        debug_assert!(self.state.is_waiting());
+        self.state = FutureState::Done;
+        if acquire_internal(internal).cancel_send_signal(self.sig.as_signal()) {
+            // Safety: signal is canceled and data is not moved, it's safe to take it back
+            return Err(SendErrorTimeout::Timeout(unsafe { self.read_local_data() }));
+        }
+        // a receiver took the signal, and it's already finished under the lock
+        if self.sig.result() == state::UNLOCKED {
+            return Ok(());
+        }
+        // Safety: the data failed to move, it's safe to take it back
+        Err(SendErrorTimeout::Closed(unsafe { self.read_local_data() }))
+    }
+    /// Cancels the operation of an unfinished core and returns its data if it's not moved to a receiver
+    pub(crate) fn withdraw(&mut self, internal: &Internal<T>) -> Option<T> {
+        if self.state.is_done() {
+            return None;
+        }
+        if self.state.is_waiting()
+            && !acquire_internal(internal).cancel_send_signal(self.sig.as_signal())
+        {
+            // a receiver took the signal, and it's already finished under the lock
+            if self.sig.result() == state::UNLOCKED {
+                // data is moved to the receiver
+                self.state = FutureState::Done;
+                return None;
+            }
+        }
+        self.state = FutureState::Done;
+        // Safety: signal is canceled, terminated, or in zero state, so data is not moved
+        Some(unsafe { self.read_local_data() })
+    }
+    /// Cancels the operation of an unfinished core and drops its data if it's not moved to a receiver
+    pub(crate) fn cancel(&mut self, internal: &Internal<T>) {
+        if !self.state.is_done() {
+            drop(self.withdraw(internal));
         }
     }
-}
-
-impl<'a, T> Future for SendFuture<'a, T> {
-    type Output = Result<(), SendError>;
 
     #[inline(always)]
-    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-        let mut this = self.as_mut().project();
-
-        match *this.state {
+    pub(crate) fn poll(
+        &mut self,
+        internal: &Internal<T>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<(), SendError<T>>> {
+        match self.state {
             FutureState::Zero => {
-                let mut internal = acquire_internal(this.internal);
-                if internal.recv_count == 0 {
+                let mut internal = acquire_internal(internal);
+                if internal.recv_count == 0 || internal.send_count == 0 {
                     let send_count = internal.send_count;
                     drop(internal);
-                    *this.state = FutureState::Done;
-                    if needs_drop::<T>() {
-                        // the data failed to move, drop it locally
-                        // Safety: the data is not moved, we are sure that it is inited in this point, it's safe to init drop it.
-                        unsafe {
-                            self.drop_local_data();
-                        }
-                    }
+                    self.state = FutureState::Done;
+                    // the data failed to move, hand it back to the caller
+                    // Safety: the data is not moved, we are sure that it is inited in this point.
+                    let data = unsafe { self.read_local_data() };
                     if send_count == 0 {
-                        return Poll::Ready(Err(SendError::Closed));
+                        return Poll::Ready(Err(SendError::Closed(data)));
                     }
-                    return Poll::Ready(Err(SendError::ReceiveClosed));
+                    return Poll::Ready(Err(SendError::ReceiveClosed(data)));
                 }
                 if let Some(first) = internal.next_recv() {
-                    drop(internal);
-                    *this.state = FutureState::Done;
+                    self.state = FutureState::Done;
                     // Safety: data is inited and available from constructor
-                    unsafe { first.send(self.read_local_data()) }
+                    let handoff = unsafe { first.send_locked(self.read_local_data()) };
+                    drop(internal);
+                    handoff.finish();
                     Poll::Ready(Ok(()))
-                } else if internal.queue.len() < internal.capacity {
-                    *this.state = FutureState::Done;
+                } else if internal.can_buffer() {
+                    self.state = FutureState::Done;
                     // Safety: data is inited and available from constructor
-                    internal.queue.push_back(unsafe { self.read_local_data() });
+                    let evicted = internal.buffer(unsafe { self.read_local_data() });
                     drop(internal);
+                    // drop the evicted object outside of the lock
+                    drop(evicted);
                     Poll::Ready(Ok(()))
                 } else {
-                    *this.state = FutureState::Waiting;
+                    self.state = FutureState::Waiting;
                     // if T is smaller than register size, we already have data in pointer address from initialization step
                     if size_of::<T>() > size_of::<*mut T>() {
-                        this.sig
-                            .set_ptr(KanalPtr::new_unchecked(this.data.as_mut_ptr()));
+                        self.sig
+                            .set_ptr(KanalPtr::new_unchecked(self.data.as_mut_ptr()));
                     }
-                    this.sig.register(cx.waker());
+                    self.sig.register(cx.waker());
                     // send directly to the waitlist
-                    internal.push_send(this.sig.as_signal());
+                    internal.push_send(self.sig.as_signal());
                     drop(internal);
                     Poll::Pending
                 }
             }
             FutureState::Waiting => {
-                if this.sig.will_wake(cx.waker()) {
+                if self.sig.will_wake(cx.waker()) {
                     // waker is same no need to update
-                    let r = this.sig.poll(cx);
+                    let r = self.sig.poll_finished();
                     match r {
                         Poll::Ready(v) => {
-                            *this.state = FutureState::Done;
+                            self.state = FutureState::Done;
                             if v == state::UNLOCKED {
                                 return Poll::Ready(Ok(()));
                             }
-                            if needs_drop::<T>() {
-                                // the data failed to move, drop it locally
-                                // Safety: the data is not moved, we are sure that it is inited in this point, it's safe to init drop it.
-                                unsafe { self.drop_local_data() };
-                            }
-                            Poll::Ready(Err(SendError::Closed))
+                            // the data failed to move, hand it back to the caller
+                            // Safety: the data is not moved, we are sure that it is inited in this point.
+                            Poll::Ready(Err(SendError::Closed(unsafe { self.read_local_data() })))
                         }
                         Poll::Pending => Poll::Pending,
                     }
                 } else {
                     // Waker is changed and we need to update waker in the waiting list
                     {
-                        let mut internal = acquire_internal(this.internal);
-                        if internal.send_signal_exists(this.sig.as_signal()) {
+                        let internal = acquire_internal(internal);
+                        if internal.send_signal_exists(self.sig.as_signal()) {
                             // signal is not shared with other thread yet so it's safe to update waker locally
-                            this.sig.register(cx.waker());
+                            self.sig.register(cx.waker());
                             return Poll::Pending;
                         }
                     }
-                    // signal is already shared, and data will be available shortly, so wait synchronously and return the result
-                    // note: it's not possible safely to update waker after the signal is shared, but we know data will be ready shortly,
-                    //   we can wait synchronously and receive it.
-                    *this.state = FutureState::Done;
-                    if this.sig.wait_indefinitely() == state::UNLOCKED {
+                    // a receiver took the signal, and it's already finished under the lock
+                    self.state = FutureState::Done;
+                    if self.sig.result() == state::UNLOCKED {
                         return Poll::Ready(Ok(()));
                     }
-                    // the data failed to move, drop it locally
-                    // Safety: the data is not moved, we are sure that it is inited in this point, it's safe to init drop it.
-                    if needs_drop::<T>() {
-                        unsafe {
-                            self.drop_local_data();
-                        }
-                    }
-                    Poll::Ready(Err(SendError::Closed))
+                    // the data failed to move, hand it back to the caller
+                    // Safety: the data is not moved, we are sure that it is inited in this point.
+                    Poll::Ready(Err(SendError::Closed(unsafe { self.read_local_data() })))
                 }
             }
             _ => {
@@ -197,38 +236,96 @@ impl<'a, T> Future for SendFuture<'a, T> {
 }
 
 pin_project! {
-    /// Receive future to receive an object from the channel asynchronously
-    /// It must be polled to perform receive action
+    /// Send future to send an object to the channel asynchronously
+    /// It must be polled to perform send action
+    ///
+    /// The future is `!Unpin` as its signal is linked in the channel waitlist while it waits,
+    ///  pin it with `Box::pin` or `std::pin::pin!` to poll it manually.
+    /// ```compile_fail
+    /// fn assert_unpin<T: Unpin>(_: &T) {}
+    /// let (s, _r) = kanal::bounded_async::<u64>(0);
+    /// assert_unpin(&s.send(1));
+    /// ```
     #[must_use = "futures do nothing unless you .await or poll them"]
-    pub struct ReceiveFuture<'a, T> {
-        pub(crate) state: FutureState,
+    pub struct SendFuture<'a, T> {
         pub(crate) internal: &'a Internal<T>,
+        // pinned as its signal is linked in the channel waitlist while it waits
         #[pin]
-        pub(crate) sig: AsyncSignal<T>,
-        pub(crate) data: MaybeUninit<T>,
+        pub(crate) core: SendCore<T>,
     }
-    impl<'a,T> PinnedDrop for ReceiveFuture<'a,T> {
-        fn drop(mut this: Pin<&mut Self>) {
-            if this.state.is_waiting() {
-                // try to cancel recv signal
-                if !acquire_internal(this.internal).cancel_recv_signal(this.sig.as_signal()) {
-                    // a sender got signal ownership, receiver should wait until the response
-                    if this.sig.wait_indefinitely() == state::UNLOCKED {
-                        // got ownership of data that is not going to be used ever again, so drop it
-                        if needs_drop::<T>(){
-                            // Safety: data is not moved it's safe to drop it
-                            unsafe {
-                                this.drop_local_data();
-                            }
-                        }
-                    }
-                }
-            }
+    impl<'a,T> PinnedDrop for SendFuture<'a,T> {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+            this.core.get().cancel(this.internal);
         }
     }
 }
 
-impl<'a, T> ReceiveFuture<'a, T> {
+impl<'a, T> SendFuture<'a, T> {
+    #[inline(always)]
+    pub(crate) fn new_ref(internal: &'a Internal<T>, data: T) -> Self {
+        Self {
+            internal,
+            core: SendCore::new(data),
+        }
+    }
+    /// Cancels the send operation and returns the object if it's not delivered to a receiver yet.
+    /// The object is either withdrawn from the channel waitlist and handed back, or it's already delivered and `None` is returned,
+    ///  it also returns `None` if the future already returned its result. The future must not be polled after cancellation.
+    /// It takes the pinned future, as a waiting future can't be moved out of its pin to get the object back.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// # use std::time::Duration;
+    /// let (s, r) = kanal::bounded_async(0);
+    /// let mut future = Box::pin(s.send(1));
+    /// // no receiver is waiting, so the send is still pending after the timeout
+    /// assert!(tokio::time::timeout(Duration::from_millis(10), future.as_mut()).await.is_err());
+    /// assert_eq!(future.as_mut().cancel(), Some(1));
+    /// assert!(r.is_empty());
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    pub fn cancel(self: Pin<&mut Self>) -> Option<T> {
+        let this = self.project();
+        this.core.get().withdraw(this.internal)
+    }
+}
+
+impl<'a, T> Future for SendFuture<'a, T> {
+    type Output = Result<(), SendError<T>>;
+
+    #[inline(always)]
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        this.core.get().poll(this.internal, cx)
+    }
+}
+
+/// State machine of an asynchronous receive operation, it's shared between receive futures and streams.
+/// The core must stay in the same memory location while it's waiting, as its signal is registered in the channel waitlist.
+pub(crate) struct ReceiveCore<T> {
+    state: FutureState,
+    sig: AsyncSignal<T>,
+    data: MaybeUninit<T>,
+}
+
+impl<T> ReceiveCore<T> {
+    #[inline(always)]
+    pub(crate) fn new() -> Self {
+        Self {
+            state: FutureState::Zero,
+            sig: AsyncSignal::new(),
+            data: MaybeUninit::uninit(),
+        }
+    }
+    /// Returns the core of a pinned future, methods of the core never move it
+    #[inline(always)]
+    pub(crate) fn get(self: Pin<&mut Self>) -> &mut Self {
+        // Safety: the core is only accessed through references, and it's not moved out of its pin
+        unsafe { self.get_unchecked_mut() }
+    }
     #[inline(always)]
     unsafe fn read_local_data(&self) -> T {
         if size_of::<T>() > size_of::<*mut T>() {
@@ -246,34 +343,60 @@ impl<'a, T> ReceiveFuture<'a, T> {
             self.sig.read_and_drop_ptr()
         }
     }
-
-    #[inline(always)]
-    pub(crate) fn new_ref(internal: &'a Internal<T>) -> Self {
-        Self {
-            state: FutureState::Zero,
-            sig: AsyncSignal::new(),
-            internal,
-            data: MaybeUninit::uninit(),
-        }
-    }
-
-    fn reset(&mut self) {
+    /// Resets a finished core to be reused for the next receive operation
+    pub(crate) fn reset(&mut self) {
         self.state = FutureState::Zero;
         self.sig = AsyncSignal::new()
     }
-}
-
-impl<'a, T> Future for ReceiveFuture<'a, T> {
-    type Output = Result<T, ReceiveError>;
+    /// Withdraws the signal of a waiting core from the channel waitlist after its deadline is reached.
+    /// If a sender already took the signal, it returns the result of the handoff instead.
+    pub(crate) fn cancel_timeout(
+        &mut self,
+        internal: &Internal<T>,
+    ) -> Result<T, ReceiveErrorTimeout> {
+        debug_assert!(self.state.is_waiting());
+        self.state = FutureState::Done;
+        if acquire_internal(internal).cancel_recv_signal(self.sig.as_signal()) {
+            return Err(ReceiveErrorTimeout::Timeout);
+        }
+        // a sender took the signal, and it's already finished under the lock
+        if self.sig.result() == state::UNLOCKED {
+            // Safety: data is moved to the core by the sender
+            return Ok(unsafe { self.read_local_data() });
+        }
+        Err(terminated_recv_error(internal).into())
+    }
+    /// Cancels the operation of a waiting core, drops the received data if a sender already moved it to the core
+    pub(crate) fn cancel(&mut self, internal: &Internal<T>) {
+        if self.state.is_waiting() {
+            self.state = FutureState::Done;
+            // try to cancel recv signal
+            if !acquire_internal(internal).cancel_recv_signal(self.sig.as_signal()) {
+                // a sender took the signal, and it's already finished under the lock
+                if self.sig.result() == state::UNLOCKED {
+                    // got ownership of data that is not going to be used ever again, so drop it
+                    if needs_drop::<T>() {
+                        // Safety: data is not moved it's safe to drop it
+                        unsafe {
+                            self.drop_local_data();
+                        }
+                    }
+                }
+            }
+        }
+    }
 
     #[inline(always)]
-    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-        let mut this = self.as_mut().project();
-        match this.state {
+    pub(crate) fn poll(
+        &mut self,
+        internal: &Internal<T>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<T, ReceiveError>> {
+        match self.state {
             FutureState::Zero => {
-                let mut internal = acquire_internal(this.internal);
+                let mut internal = acquire_internal(internal);
                 if internal.recv_count == 0 {
-                    *this.state = FutureState::Done;
+                    self.state = FutureState::Done;
                     return Poll::Ready(Err(ReceiveError::Closed));
                 }
                 if let Some(v) = internal.queue.pop_front() {
@@ -282,61 +405,63 @@ impl<'a, T> Future for ReceiveFuture<'a, T> {
                         unsafe { internal.queue.push_back(p.recv()) }
                     }
                     drop(internal);
-                    *this.state = FutureState::Done;
+                    self.state = FutureState::Done;
                     Poll::Ready(Ok(v))
                 } else if let Some(p) = internal.next_send() {
+                    self.state = FutureState::Done;
+                    // Safety: it's safe to receive from owned signal once
+                    let (data, handoff) = unsafe { p.recv_locked() };
                     drop(internal);
-                    *this.state = FutureState::Done;
-                    unsafe { Poll::Ready(Ok(p.recv())) }
+                    handoff.finish();
+                    Poll::Ready(Ok(data))
                 } else {
                     if internal.send_count == 0 {
-                        *this.state = FutureState::Done;
+                        self.state = FutureState::Done;
                         return Poll::Ready(Err(ReceiveError::SendClosed));
                     }
-                    *this.state = FutureState::Waiting;
+                    self.state = FutureState::Waiting;
                     if size_of::<T>() > size_of::<*mut T>() {
                         // if type T smaller than register size, it does not need pointer setup as data will be stored in register address object
-                        this.sig
-                            .set_ptr(KanalPtr::new_unchecked(this.data.as_mut_ptr()));
+                        self.sig
+                            .set_ptr(KanalPtr::new_unchecked(self.data.as_mut_ptr()));
                     }
-                    this.sig.register(cx.waker());
+                    self.sig.register(cx.waker());
                     // no active waiter so push to the queue
-                    internal.push_recv(this.sig.as_signal());
+                    internal.push_recv(self.sig.as_signal());
                     drop(internal);
                     Poll::Pending
                 }
             }
             FutureState::Waiting => {
-                if this.sig.will_wake(cx.waker()) {
+                if self.sig.will_wake(cx.waker()) {
                     // waker is same no need to update
-                    let r = this.sig.poll(cx);
+                    let r = self.sig.poll_finished();
                     match r {
                         Poll::Ready(v) => {
-                            *this.state = FutureState::Done;
+                            self.state = FutureState::Done;
                             if v == state::UNLOCKED {
                                 return Poll::Ready(Ok(unsafe { self.read_local_data() }));
                             }
-                            Poll::Ready(Err(ReceiveError::Closed))
+                            Poll::Ready(Err(terminated_recv_error(internal)))
                         }
                         Poll::Pending => Poll::Pending,
                     }
                 } else {
                     // the Waker is changed and we need to update waker in the waiting list
                     {
-                        let mut internal = acquire_internal(this.internal);
-                        if internal.recv_signal_exists(this.sig.as_signal()) {
+                        let internal = acquire_internal(internal);
+                        if internal.recv_signal_exists(self.sig.as_signal()) {
                             // signal is not shared with other thread yet so it's safe to update waker locally
-                            this.sig.register(cx.waker());
+                            self.sig.register(cx.waker());
                             return Poll::Pending;
                         }
                     }
-                    // the signal is already shared, and data will be available shortly, so wait synchronously and return the result
-                    // note: it's not possible safely to update waker after the signal is shared, but we know data will be ready shortly,
-                    //   we can wait synchronously and receive it.
-                    if this.sig.wait_indefinitely() == state::UNLOCKED {
+                    // a sender took the signal, and it's already finished under the lock
+                    self.state = FutureState::Done;
+                    if self.sig.result() == state::UNLOCKED {
                         return Poll::Ready(Ok(unsafe { self.read_local_data() }));
                     }
-                    Poll::Ready(Err(ReceiveError::Closed))
+                    Poll::Ready(Err(terminated_recv_error(internal)))
                 }
             }
             _ => {
@@ -346,9 +471,693 @@ impl<'a, T> Future for ReceiveFuture<'a, T> {
     }
 }
 
+pin_project! {
+    /// Receive future to receive an object from the channel asynchronously
+    /// It must be polled to perform receive action
+    ///
+    /// The future is `!Unpin` as its signal is linked in the channel waitlist while it waits,
+    ///  pin it with `Box::pin` or `std::pin::pin!` to poll it manually.
+    /// ```compile_fail
+    /// fn assert_unpin<T: Unpin>(_: &T) {}
+    /// let (_s, r) = kanal::bounded_async::<u64>(0);
+    /// assert_unpin(&r.recv());
+    /// ```
+    #[must_use = "futures do nothing unless you .await or poll them"]
+    pub struct ReceiveFuture<'a, T> {
+        pub(crate) internal: &'a Internal<T>,
+        // pinned as its signal is linked in the channel waitlist while it waits
+        #[pin]
+        pub(crate) core: ReceiveCore<T>,
+    }
+    impl<'a,T> PinnedDrop for ReceiveFuture<'a,T> {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+            this.core.get().cancel(this.internal);
+        }
+    }
+}
+
+impl<'a, T> ReceiveFuture<'a, T> {
+    #[inline(always)]
+    pub(crate) fn new_ref(internal: &'a Internal<T>) -> Self {
+        Self {
+            internal,
+            core: ReceiveCore::new(),
+        }
+    }
+}
+
+impl<'a, T> Future for ReceiveFuture<'a, T> {
+    type Output = Result<T, ReceiveError>;
+
+    #[inline(always)]
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        this.core.get().poll(this.internal, cx)
+    }
+}
+
+pin_project! {
+    /// Owned send future to send an object to the channel asynchronously
+    /// It holds its own reference to the channel, so it's `'static` for `'static` T and can be spawned or stored freely.
+    /// It must be polled to perform send action
+    #[must_use = "futures do nothing unless you .await or poll them"]
+    pub struct OwnedSendFuture<T> {
+        pub(crate) internal: Internal<T>,
+        // pinned as its signal is linked in the channel waitlist while it waits
+        #[pin]
+        pub(crate) core: SendCore<T>,
+    }
+    impl<T> PinnedDrop for OwnedSendFuture<T> {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+            this.core.get().cancel(this.internal);
+        }
+    }
+}
+
+impl<T> Debug for OwnedSendFuture<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OwnedSendFuture {{ .. }}")
+    }
+}
+
+impl<T> OwnedSendFuture<T> {
+    #[inline(always)]
+    pub(crate) fn new_owned(internal: Internal<T>, data: T) -> Self {
+        Self {
+            internal,
+            core: SendCore::new(data),
+        }
+    }
+    /// Cancels the send operation and returns the object if it's not delivered to a receiver yet,
+    ///  see [`SendFuture::cancel`]. The future must not be polled after cancellation.
+    pub fn cancel(self: Pin<&mut Self>) -> Option<T> {
+        let this = self.project();
+        this.core.get().withdraw(this.internal)
+    }
+}
+
+impl<T> Future for OwnedSendFuture<T> {
+    type Output = Result<(), SendError<T>>;
+
+    #[inline(always)]
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        this.core.get().poll(this.internal, cx)
+    }
+}
+
+pin_project! {
+    /// Owned receive future to receive an object from the channel asynchronously
+    /// It holds its own reference to the channel, so it's `'static` for `'static` T and can be spawned or stored freely.
+    /// It must be polled to perform receive action
+    #[must_use = "futures do nothing unless you .await or poll them"]
+    pub struct OwnedReceiveFuture<T> {
+        pub(crate) internal: Internal<T>,
+        // pinned as its signal is linked in the channel waitlist while it waits
+        #[pin]
+        pub(crate) core: ReceiveCore<T>,
+    }
+    impl<T> PinnedDrop for OwnedReceiveFuture<T> {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+            this.core.get().cancel(this.internal);
+        }
+    }
+}
+
+impl<T> Debug for OwnedReceiveFuture<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OwnedReceiveFuture {{ .. }}")
+    }
+}
+
+impl<T> OwnedReceiveFuture<T> {
+    #[inline(always)]
+    pub(crate) fn new_owned(internal: Internal<T>) -> Self {
+        Self {
+            internal,
+            core: ReceiveCore::new(),
+        }
+    }
+}
+
+impl<T> Future for OwnedReceiveFuture<T> {
+    type Output = Result<T, ReceiveError>;
+
+    #[inline(always)]
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        this.core.get().poll(this.internal, cx)
+    }
+}
+
+/// Wakers of the tasks that poll a shared endpoint, the slot of the endpoint registers a single waker in the channel
+///  that wakes all of them, so a task polling the same handle doesn't displace the waker of another task.
+struct TaskWakers {
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl TaskWakers {
+    /// Adds the waker of a task, wakers that wake the same task are only added once
+    fn add(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock();
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+
+    /// Removes the waker of a task that completed its operation, returns true if no other task is waiting
+    fn remove(&self, waker: &Waker) -> bool {
+        let mut wakers = self.wakers.lock();
+        wakers.retain(|w| !w.will_wake(waker));
+        wakers.is_empty()
+    }
+}
+
+impl std::task::Wake for TaskWakers {
+    fn wake(self: std::sync::Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &std::sync::Arc<Self>) {
+        // woken tasks add their wakers again if they still have to wait
+        let wakers = std::mem::take(&mut *self.wakers.lock());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+/// State of a poll slot, the waiter and the object in flight of the endpoint with the wakers of the tasks that poll it.
+pub(crate) struct SlotState<T> {
+    tasks: std::sync::Arc<TaskWakers>,
+    // wakes every task in `tasks`, it's the waker that is registered in the channel
+    waker: Waker,
+    waiter: ReadyWaiter<T>,
+    core: SendCore<T>,
+}
+
+impl<T> SlotState<T> {
+    fn new() -> Self {
+        let tasks = std::sync::Arc::new(TaskWakers {
+            wakers: Mutex::new(Vec::new()),
+        });
+        Self {
+            waker: Waker::from(tasks.clone()),
+            tasks,
+            waiter: ReadyWaiter::new(),
+            core: SendCore::done(),
+        }
+    }
+
+    /// Checks whether a receive operation can complete without waiting, and waits in the receive waitlist if it can't
+    pub(crate) fn poll_recv_ready(
+        &mut self,
+        internal: &Internal<T>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<()> {
+        // the task is added before the channel is checked, so a notification right after the check wakes it
+        self.tasks.add(cx.waker());
+        self.waiter.poll_recv_ready(internal, &self.waker)
+    }
+
+    /// Removes the task after its receive is done, the waiter leaves the waitlist if no other task is waiting
+    pub(crate) fn finish_recv(&mut self, internal: &Internal<T>, cx: &mut std::task::Context<'_>) {
+        // a waiter of an earlier poll is not notified if another receiver took its object
+        if self.tasks.remove(cx.waker()) {
+            self.waiter.unregister_recv(internal);
+        }
+    }
+
+    /// Drives the object in flight to the channel with the waker of the slot, it's ready when there is no object in flight
+    fn poll_in_flight(&mut self, internal: &Internal<T>) -> Poll<Result<(), SendError<T>>> {
+        if self.core.is_done() {
+            return Poll::Ready(Ok(()));
+        }
+        self.core
+            .poll(internal, &mut std::task::Context::from_waker(&self.waker))
+    }
+
+    /// Removes the task after its send poll is done, the waiter leaves the waitlist if no other task is waiting
+    fn finish_send(&mut self, internal: &Internal<T>, cx: &mut std::task::Context<'_>) {
+        if self.tasks.remove(cx.waker()) {
+            self.waiter.unregister_send(internal);
+        }
+    }
+
+    /// Drives the object in flight to the channel
+    pub(crate) fn poll_flush(
+        &mut self,
+        internal: &Internal<T>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<(), SendError<T>>> {
+        self.tasks.add(cx.waker());
+        let res = self.poll_in_flight(internal);
+        if res.is_ready() {
+            self.finish_send(internal, cx);
+        }
+        res
+    }
+
+    /// Drives the object in flight to the channel, then checks the channel for room to start the next send
+    pub(crate) fn poll_send_ready(
+        &mut self,
+        internal: &Internal<T>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<(), SendError<Option<T>>>> {
+        self.tasks.add(cx.waker());
+        let res = match self.poll_in_flight(internal) {
+            Poll::Ready(Ok(())) => self.waiter.poll_send_ready(internal, &self.waker),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(in_flight_error(err))),
+            Poll::Pending => Poll::Pending,
+        };
+        if res.is_ready() {
+            self.finish_send(internal, cx);
+        }
+        res
+    }
+
+    /// Starts sending the object, the object is handed back with `Full` if the object of the previous send is still in flight
+    pub(crate) fn start_send(
+        &mut self,
+        internal: &Internal<T>,
+        data: T,
+    ) -> Result<(), TrySendError<T>> {
+        if !self.core.is_done() {
+            return Err(TrySendError::Full(data));
+        }
+        self.core = SendCore::new(data);
+        // the object waits in the waitlist with the waker of the slot if there is no room, so tasks polling
+        //  `poll_send_ready` are woken once it's delivered
+        match self
+            .core
+            .poll(internal, &mut std::task::Context::from_waker(&self.waker))
+        {
+            Poll::Ready(Ok(())) | Poll::Pending => Ok(()),
+            Poll::Ready(Err(SendError::Closed(data))) => Err(TrySendError::Closed(data)),
+            Poll::Ready(Err(SendError::ReceiveClosed(data))) => {
+                Err(TrySendError::ReceiveClosed(data))
+            }
+        }
+    }
+}
+
+/// Waiter slot of an async endpoint for its poll functions, the state is boxed to keep its signal addresses stable
+///  while they are waiting, and it's only allocated on the first use of the slot.
+pub(crate) struct PollSlot<T> {
+    state: Mutex<Option<Box<SlotState<T>>>>,
+}
+
+impl<T> PollSlot<T> {
+    /// Runs the function with the state of the slot under the lock of the slot,
+    ///  the lock of the slot is always acquired before the lock of the channel
+    #[inline(always)]
+    pub(crate) fn with<R>(&self, f: impl FnOnce(&mut SlotState<T>) -> R) -> R {
+        f(self
+            .state
+            .lock()
+            .get_or_insert_with(|| Box::new(SlotState::new())))
+    }
+
+    /// Removes the receive waiter of the slot from the channel, it's called when the receiver is dropped
+    pub(crate) fn cancel_recv(&mut self, internal: &Internal<T>) {
+        if let Some(state) = self.state.get_mut() {
+            state.waiter.unregister_recv(internal);
+        }
+    }
+
+    /// Removes the send waiter and drops the object in flight of the slot, it's called when the sender is dropped
+    pub(crate) fn cancel_send(&mut self, internal: &Internal<T>) {
+        if let Some(state) = self.state.get_mut() {
+            state.waiter.unregister_send(internal);
+            state.core.cancel(internal);
+        }
+    }
+}
+
+impl<T> Default for PollSlot<T> {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(None),
+        }
+    }
+}
+
+/// Returns a waker that does nothing, it's registered for operations that are started outside of a task
+///  until they are polled by one
+pub(crate) fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    // Safety: the vtable functions don't access the data pointer
+    unsafe { Waker::from_raw(clone(std::ptr::null())) }
+}
+
+/// Sink to send objects to the channel asynchronously, it owns the sender that created it.
+/// `poll_ready` is pending while the channel has no room for an object and no receiver is waiting, the sink waits
+///  for room in the channel send waitlist without committing an object, so backpressure of bounded channels applies
+///  before `start_send`. Readiness is not a reservation, if another sender takes the room first, the object of `start_send`
+///  waits in the channel waitlist and the next `poll_ready` or `poll_flush` stays pending until it's delivered.
+/// `poll_close` flushes the sink and drops its sender. Like dropping any other sender, that closes the send side of the channel
+///  and wakes waiting receivers only if the sink holds the last sender, use [`AsyncSender::close_send`] to close the channel
+///  for all of its senders.
+pub struct SendSink<T> {
+    sender: Option<AsyncSender<T>>,
+    // boxed to keep the signal address stable while the sink moves between polls
+    core: Box<SendCore<T>>,
+    // boxed to keep the entry in place while it's linked in the send waitlist
+    waiter: Box<ReadyWaiter<T>>,
+}
+
+/// Notification only waiter in a waitlist of the channel, peers notify it when the operation can make progress,
+///  e.g. receivers notify the waiter of a sink when they make room in the channel.
+/// It never takes an object, so a waiter that is not polled anymore doesn't hold back objects from other receivers.
+pub(crate) struct ReadyWaiter<T> {
+    sig: AsyncSignal<()>,
+    entry: SelectEntry<T>,
+    registered: bool,
+}
+
+impl<T> ReadyWaiter<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            sig: AsyncSignal::new(),
+            entry: SelectEntry::new(),
+            registered: false,
+        }
+    }
+
+    /// Checks whether a receive operation can complete without waiting, and waits in the receive waitlist if it can't
+    pub(crate) fn poll_recv_ready(&mut self, internal: &Internal<T>, waker: &Waker) -> Poll<()> {
+        let mut internal = acquire_internal(internal);
+        if self.registered {
+            // the entry is already unlinked if a sender notified it
+            internal.cancel_recv_signal(self.entry.as_signal());
+            self.registered = false;
+        }
+        if internal.recv_ready() {
+            return Poll::Ready(());
+        }
+        // the entry is not linked, so its signal is owned by the waiter and the waker can be updated
+        self.sig.register(waker);
+        // Safety: the entry is unlinked above, and it stays in place in its box until it's unlinked again
+        internal.push_recv(unsafe { self.entry.bind(SelectSignal::Async(&self.sig)) });
+        self.registered = true;
+        Poll::Pending
+    }
+
+    /// Removes the waiter from the receive waitlist
+    pub(crate) fn unregister_recv(&mut self, internal: &Internal<T>) {
+        if self.registered {
+            acquire_internal(internal).cancel_recv_signal(self.entry.as_signal());
+            self.registered = false;
+        }
+    }
+
+    /// Checks the channel for room to send an object, and waits in the send waitlist without an object if there is none,
+    ///  errors of a closed channel hold no object
+    pub(crate) fn poll_send_ready(
+        &mut self,
+        internal: &Internal<T>,
+        waker: &Waker,
+    ) -> Poll<Result<(), SendError<Option<T>>>> {
+        let mut internal = acquire_internal(internal);
+        if self.registered {
+            // the entry is already unlinked if a receiver notified it
+            internal.cancel_send_signal(self.entry.as_signal());
+            self.registered = false;
+        }
+        if internal.send_count == 0 {
+            return Poll::Ready(Err(SendError::Closed(None)));
+        }
+        if internal.recv_count == 0 {
+            return Poll::Ready(Err(SendError::ReceiveClosed(None)));
+        }
+        if internal.send_room() {
+            return Poll::Ready(Ok(()));
+        }
+        // the entry is not linked, so its signal is owned by the waiter and the waker can be updated
+        self.sig.register(waker);
+        // Safety: the entry is unlinked above, and it stays in place in its box until it's unlinked again
+        internal.push_send(unsafe { self.entry.bind(SelectSignal::Async(&self.sig)) });
+        self.registered = true;
+        Poll::Pending
+    }
+
+    /// Removes the waiter from the send waitlist
+    pub(crate) fn unregister_send(&mut self, internal: &Internal<T>) {
+        if self.registered {
+            acquire_internal(internal).cancel_send_signal(self.entry.as_signal());
+            self.registered = false;
+        }
+    }
+}
+
+impl<T> Debug for SendSink<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SendSink {{ .. }}")
+    }
+}
+
+/// Moves the failed in flight object of a sink inside its error
+fn in_flight_error<T>(err: SendError<T>) -> SendError<Option<T>> {
+    match err {
+        SendError::Closed(v) => SendError::Closed(Some(v)),
+        SendError::ReceiveClosed(v) => SendError::ReceiveClosed(Some(v)),
+    }
+}
+
+impl<T> SendSink<T> {
+    pub(crate) fn new(sender: AsyncSender<T>) -> Self {
+        Self {
+            sender: Some(sender),
+            core: Box::new(SendCore::done()),
+            waiter: Box::new(ReadyWaiter::new()),
+        }
+    }
+
+    /// Drives the in flight object to the channel, it's ready when there is no object in flight
+    fn poll_in_flight(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<(), SendError<Option<T>>>> {
+        if self.core.is_done() {
+            return Poll::Ready(Ok(()));
+        }
+        // an object in flight means the sink is not closed yet
+        let sender = self.sender.as_ref().unwrap();
+        self.core
+            .poll(&sender.internal, cx)
+            .map_err(in_flight_error)
+    }
+
+    /// Checks the channel for room to send an object, and waits in the send waitlist without an object if there is none
+    fn poll_room(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<(), SendError<Option<T>>>> {
+        let sender = self.sender.as_ref().unwrap();
+        self.waiter.poll_send_ready(&sender.internal, cx.waker())
+    }
+
+    /// Removes the waiter of the sink from the send waitlist
+    fn unregister(&mut self) {
+        if let Some(sender) = &self.sender {
+            self.waiter.unregister_send(&sender.internal);
+        }
+    }
+}
+
+impl<T> Sink<T> for SendSink<T> {
+    /// The failed in flight object is handed back inside the error, readiness errors of a closed sink hold no object
+    type Error = SendError<Option<T>>;
+
+    fn poll_ready(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        if this.sender.is_none() {
+            return Poll::Ready(Err(SendError::Closed(None)));
+        }
+        match this.poll_in_flight(cx) {
+            Poll::Ready(Ok(())) => this.poll_room(cx),
+            r => r,
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        let sender = match &this.sender {
+            Some(sender) => sender,
+            None => return Err(SendError::Closed(Some(item))),
+        };
+        assert!(
+            this.core.is_done(),
+            "start_send called without a successful poll_ready"
+        );
+        *this.core = SendCore::new(item);
+        // the object is sent right away if there is room, otherwise it waits in the waitlist and
+        //  the waker of the task is registered by the next poll_ready or poll_flush
+        let waker = noop_waker();
+        match this.core.poll(
+            &sender.internal,
+            &mut std::task::Context::from_waker(&waker),
+        ) {
+            Poll::Ready(r) => r.map_err(in_flight_error),
+            Poll::Pending => Ok(()),
+        }
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().poll_in_flight(cx)
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        match this.poll_in_flight(cx) {
+            Poll::Ready(r) => {
+                // release the sender, the send side of the channel gets closed if it's the last sender
+                this.unregister();
+                this.sender = None;
+                Poll::Ready(r)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T> Drop for SendSink<T> {
+    fn drop(&mut self) {
+        self.unregister();
+        if let Some(sender) = &self.sender {
+            self.core.cancel(&sender.internal);
+        }
+    }
+}
+
+/// Future that resolves once a receive operation on the channel can complete without waiting, or the deadline is reached.
+/// It waits in the receive waitlist without taking an object, so batch receives drain the channel under a single lock after each wake up.
+#[must_use = "futures do nothing unless you .await or poll them"]
+pub(crate) struct RecvReadyFuture<'a, T> {
+    internal: &'a Internal<T>,
+    // boxed to keep the entry in place while it's linked in the receive waitlist
+    waiter: Box<ReadyWaiter<T>>,
+    delay: Option<Delay>,
+}
+
+impl<'a, T> RecvReadyFuture<'a, T> {
+    pub(crate) fn new(internal: &'a Internal<T>, deadline: Option<Instant>) -> Self {
+        Self {
+            internal,
+            waiter: Box::new(ReadyWaiter::new()),
+            delay: deadline.map(Delay::new),
+        }
+    }
+}
+
+impl<T> Future for RecvReadyFuture<'_, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if let Some(delay) = &mut this.delay {
+            if delay.poll_expired(cx) {
+                return Poll::Ready(());
+            }
+        }
+        this.waiter.poll_recv_ready(this.internal, cx.waker())
+    }
+}
+
+impl<T> Drop for RecvReadyFuture<'_, T> {
+    fn drop(&mut self) {
+        self.waiter.unregister_recv(self.internal);
+    }
+}
+
+pin_project! {
+    /// Send future with a deadline to send an object to the channel asynchronously
+    /// It must be polled to perform send action, the deadline is tracked by kanal timer and does not depend on the runtime
+    #[must_use = "futures do nothing unless you .await or poll them"]
+    pub struct SendTimeoutFuture<'a, T> {
+        #[pin]
+        pub(crate) future: SendFuture<'a, T>,
+        pub(crate) delay: Delay,
+    }
+}
+
+impl<'a, T> Debug for SendTimeoutFuture<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SendTimeoutFuture {{ .. }}")
+    }
+}
+
+impl<'a, T> Future for SendTimeoutFuture<'a, T> {
+    type Output = Result<(), SendErrorTimeout<T>>;
+
+    #[inline(always)]
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        if let Poll::Ready(r) = this.future.as_mut().poll(cx) {
+            return Poll::Ready(r.map_err(SendErrorTimeout::from));
+        }
+        if this.delay.poll_expired(cx) {
+            let future = this.future.project();
+            return Poll::Ready(future.core.get().cancel_timeout(future.internal));
+        }
+        Poll::Pending
+    }
+}
+
+pin_project! {
+    /// Receive future with a deadline to receive an object from the channel asynchronously
+    /// It must be polled to perform receive action, the deadline is tracked by kanal timer and does not depend on the runtime
+    #[must_use = "futures do nothing unless you .await or poll them"]
+    pub struct ReceiveTimeoutFuture<'a, T> {
+        #[pin]
+        pub(crate) future: ReceiveFuture<'a, T>,
+        pub(crate) delay: Delay,
+    }
+}
+
+impl<'a, T> Debug for ReceiveTimeoutFuture<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ReceiveTimeoutFuture {{ .. }}")
+    }
+}
+
+impl<'a, T> Future for ReceiveTimeoutFuture<'a, T> {
+    type Output = Result<T, ReceiveErrorTimeout>;
+
+    #[inline(always)]
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        if let Poll::Ready(r) = this.future.as_mut().poll(cx) {
+            return Poll::Ready(r.map_err(ReceiveErrorTimeout::from));
+        }
+        if this.delay.poll_expired(cx) {
+            let future = this.future.project();
+            return Poll::Ready(future.core.get().cancel_timeout(future.internal));
+        }
+        Poll::Pending
+    }
+}
+
 /// Receive stream
 pub struct ReceiveStream<'a, T: 'a> {
-    future: ReceiveFuture<'a, T>,
+    // boxed to keep the signal address stable while the stream moves between polls
+    core: Box<ReceiveCore<T>>,
     terminated: bool,
     receiver: &'a AsyncReceiver<T>,
 }
@@ -361,20 +1170,21 @@ impl<'a, T> Stream for ReceiveStream<'a, T> {
     type Item = T;
 
     fn poll_next(
-        mut self: Pin<&mut Self>,
+        self: Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        if self.terminated {
+        let this = self.get_mut();
+        if this.terminated {
             return Poll::Ready(None);
         }
-        match Pin::new(&mut self.future).poll(cx) {
+        match this.core.poll(&this.receiver.internal, cx) {
             Poll::Ready(res) => match res {
                 Ok(d) => {
-                    self.future.reset();
+                    this.core.reset();
                     Poll::Ready(Some(d))
                 }
                 Err(_) => {
-                    self.terminated = true;
+                    this.terminated = true;
                     Poll::Ready(None)
                 }
             },
@@ -392,9 +1202,201 @@ impl<'a, T> FusedStream for ReceiveStream<'a, T> {
 impl<'a, T> ReceiveStream<'a, T> {
     pub(crate) fn new_borrowed(receiver: &'a AsyncReceiver<T>) -> Self {
         ReceiveStream {
-            future: receiver.recv(),
+            core: Box::new(ReceiveCore::new()),
+            terminated: false,
+            receiver,
+        }
+    }
+}
+
+impl<'a, T> Drop for ReceiveStream<'a, T> {
+    fn drop(&mut self) {
+        self.core.cancel(&self.receiver.internal);
+    }
+}
+
+/// Owned receive stream, it owns the receiver that created it, so it can be spawned or stored freely.
+pub struct OwnedReceiveStream<T> {
+    // boxed to keep the signal address stable while the stream moves between polls
+    core: Box<ReceiveCore<T>>,
+    terminated: bool,
+    receiver: AsyncReceiver<T>,
+}
+impl<T> Debug for OwnedReceiveStream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OwnedReceiveStream {{ .. }}")
+    }
+}
+impl<T> Stream for OwnedReceiveStream<T> {
+    type Item = T;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.terminated {
+            return Poll::Ready(None);
+        }
+        match this.core.poll(&this.receiver.internal, cx) {
+            Poll::Ready(res) => match res {
+                Ok(d) => {
+                    this.core.reset();
+                    Poll::Ready(Some(d))
+                }
+                Err(_) => {
+                    this.terminated = true;
+                    Poll::Ready(None)
+                }
+            },
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T> FusedStream for OwnedReceiveStream<T> {
+    fn is_terminated(&self) -> bool {
+        self.receiver.is_terminated()
+    }
+}
+
+impl<T> OwnedReceiveStream<T> {
+    pub(crate) fn new_owned(receiver: AsyncReceiver<T>) -> Self {
+        OwnedReceiveStream {
+            core: Box::new(ReceiveCore::new()),
             terminated: false,
             receiver,
         }
     }
+    /// Returns the receiver of the stream back, an unfinished receive operation of the stream is canceled.
+    pub fn into_inner(self) -> AsyncReceiver<T> {
+        let mut this = ManuallyDrop::new(self);
+        let this = &mut *this;
+        this.core.cancel(&this.receiver.internal);
+        // Safety: the stream is not dropped, so each field is moved out of it once
+        unsafe {
+            drop(std::ptr::read(&this.core));
+            std::ptr::read(&this.receiver)
+        }
+    }
+}
+
+impl<T> Drop for OwnedReceiveStream<T> {
+    fn drop(&mut self) {
+        self.core.cancel(&self.receiver.internal);
+    }
+}
+
+/// Future to receive an object from any of several async receivers, see [`recv_any`](crate::recv_any)
+#[must_use = "futures do nothing unless you .await or poll them"]
+pub struct RecvAnyFuture<'a, T> {
+    receivers: &'a [&'a AsyncReceiver<T>],
+    // boxed to keep the signal address stable while the future moves between polls
+    sig: Option<Box<AsyncSignal<()>>>,
+    /// Registrations of the signal in the waitlists of the receivers, one for each receiver
+    entries: Box<[SelectEntry<T>]>,
+    /// Count of receivers from the start of the slice that have the signal in their waitlist
+    registered: usize,
+    done: bool,
+}
+
+impl<T> Debug for RecvAnyFuture<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RecvAnyFuture {{ .. }}")
+    }
+}
+
+impl<'a, T> RecvAnyFuture<'a, T> {
+    pub(crate) fn new(receivers: &'a [&'a AsyncReceiver<T>]) -> Self {
+        Self {
+            receivers,
+            sig: None,
+            entries: receivers.iter().map(|_| SelectEntry::new()).collect(),
+            registered: 0,
+            done: false,
+        }
+    }
+    /// Removes the signal from the waitlists, peers only notify the signal under the lock,
+    ///  so after this call the signal is owned by the future again and its waker can be updated.
+    fn unregister(&mut self) {
+        for (receiver, entry) in self.receivers[..self.registered].iter().zip(&*self.entries) {
+            acquire_internal(&receiver.internal).cancel_recv_signal(entry.as_signal());
+        }
+        self.registered = 0;
+    }
+}
+
+impl<T> Future for RecvAnyFuture<'_, T> {
+    type Output = (usize, Result<T, ReceiveError>);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.done {
+            panic!("polled after result is already returned")
+        }
+        let len = this.receivers.len();
+        loop {
+            this.unregister();
+            // closed receivers are skipped while another one can still receive,
+            //  and the first closed receiver of the slice is reported once all of them are closed
+            let mut closed: Option<(usize, ReceiveError)> = None;
+            let mut closed_count = 0;
+            // start from a random receiver to keep the receive fair among the channels
+            let start = backoff::random_u32() as usize % len;
+            for index in (start..len).chain(0..start) {
+                let err = match this.receivers[index].try_recv() {
+                    Ok(data) => {
+                        this.done = true;
+                        return Poll::Ready((index, Ok(data)));
+                    }
+                    Err(TryRecvError::Empty | TryRecvError::Contended) => continue,
+                    Err(TryRecvError::Closed) => ReceiveError::Closed,
+                    Err(TryRecvError::SendClosed) => ReceiveError::SendClosed,
+                };
+                closed_count += 1;
+                match closed {
+                    Some((first, _)) if first < index => {}
+                    _ => closed = Some((index, err)),
+                }
+            }
+            if closed_count == len {
+                this.done = true;
+                let (index, err) = closed.unwrap();
+                return Poll::Ready((index, Err(err)));
+            }
+            let sig = this.sig.get_or_insert_with(|| Box::new(AsyncSignal::new()));
+            sig.register(cx.waker());
+            let sig = &**sig as *const AsyncSignal<()>;
+            let mut waiting = 0;
+            this.registered = this
+                .receivers
+                .iter()
+                .zip(&*this.entries)
+                .take_while(|(receiver, entry)| {
+                    let mut internal = acquire_internal(&receiver.internal);
+                    if internal.recv_closed() {
+                        return true;
+                    }
+                    if internal.recv_ready() {
+                        return false;
+                    }
+                    // Safety: entries are unregistered before they are registered again
+                    internal.push_recv(unsafe { entry.bind(SelectSignal::Async(sig)) });
+                    waiting += 1;
+                    true
+                })
+                .count();
+            if this.registered == len && waiting > 0 {
+                return Poll::Pending;
+            }
+            // one of the receivers got ready or all of them got closed while registering, try again
+        }
+    }
+}
+
+impl<T> Drop for RecvAnyFuture<'_, T> {
+    fn drop(&mut self) {
+        // the signal never owns data, so withdrawing it from the waitlists is all that cancellation needs
+        self.unregister();
+    }
 }
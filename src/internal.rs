@@ -1,4 +1,10 @@
-use std::{collections::VecDeque, sync::Arc};
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{
+        atomic::{AtomicU32, AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+};
 
 #[cfg(not(feature = "std-mutex"))]
 use crate::mutex::{Mutex, MutexGuard};
@@ -6,28 +12,126 @@ use crate::mutex::{Mutex, MutexGuard};
 use std::sync::{Mutex, MutexGuard};
 //use spin::mutex::Mutex;
 
-use crate::signal::Signal;
+use crate::queue::{OverflowPolicy, Queue};
+use crate::signal::{Signal, SPIN_COUNT};
+use crate::waitlist::WaitList;
+use crate::{ChannelMetrics, ReceiveError};
 
-pub type Internal<T> = Arc<Mutex<ChannelInternal<T>>>;
+pub type Internal<T> = Arc<SharedInternal<T>>;
+/// Internal of a channel that is referenced by weak handles, it doesn't keep the channel allocated
+pub type WeakInternal<T> = Weak<SharedInternal<T>>;
+
+/// Channel internal behind its mutex along with a snapshot of the channel state,
+///  the mutex is the source of truth and the snapshot serves state queries without acquiring it
+pub struct SharedInternal<T> {
+    mutex: Mutex<ChannelInternal<T>>,
+    pub snapshot: Snapshot,
+}
+
+/// Copy of the channel state that is published on every release of the channel mutex,
+///  so it lags behind the mutex-guarded state only while an operation is in progress
+pub struct Snapshot {
+    /// The capacity of the channel buffer, it never changes after construction
+    pub capacity: usize,
+    len: AtomicUsize,
+    recv_count: AtomicU32,
+    send_count: AtomicU32,
+}
+
+impl Snapshot {
+    fn publish<T>(&self, internal: &ChannelInternal<T>) {
+        self.len.store(internal.queue.len(), Ordering::Relaxed);
+        // counters rarely change, so they are only written on change to keep the cache line of readers valid,
+        //  and the release store makes the length of the same release visible to readers of the counter
+        if self.recv_count.load(Ordering::Relaxed) != internal.recv_count {
+            self.recv_count
+                .store(internal.recv_count, Ordering::Release);
+        }
+        if self.send_count.load(Ordering::Relaxed) != internal.send_count {
+            self.send_count
+                .store(internal.send_count, Ordering::Release);
+        }
+    }
+    /// Returns count of buffered objects
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+    /// Returns count of alive receivers
+    #[inline(always)]
+    pub fn recv_count(&self) -> u32 {
+        self.recv_count.load(Ordering::Acquire)
+    }
+    /// Returns count of alive senders
+    #[inline(always)]
+    pub fn send_count(&self) -> u32 {
+        self.send_count.load(Ordering::Acquire)
+    }
+}
+
+/// Mutex guard of the channel internal, it publishes the channel state to the snapshot before the mutex is released
+pub struct InternalGuard<'a, T> {
+    guard: MutexGuard<'a, ChannelInternal<T>>,
+    snapshot: &'a Snapshot,
+}
+
+impl<T> Deref for InternalGuard<'_, T> {
+    type Target = ChannelInternal<T>;
+
+    #[inline(always)]
+    fn deref(&self) -> &ChannelInternal<T> {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for InternalGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut ChannelInternal<T> {
+        &mut self.guard
+    }
+}
+
+impl<T> Drop for InternalGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.snapshot.publish(&self.guard);
+    }
+}
 
 /// Acquire mutex guard on channel internal for use in channel operations
 #[inline(always)]
-pub fn acquire_internal<T>(internal: &'_ Internal<T>) -> MutexGuard<'_, ChannelInternal<T>> {
+pub fn acquire_internal<T>(internal: &'_ Internal<T>) -> InternalGuard<'_, T> {
     #[cfg(not(feature = "std-mutex"))]
-    return internal.lock();
+    let guard = internal.mutex.lock();
     #[cfg(feature = "std-mutex")]
-    internal.lock().unwrap()
+    let guard = internal.mutex.lock().unwrap();
+    InternalGuard {
+        guard,
+        snapshot: &internal.snapshot,
+    }
 }
 
 /// Tries to acquire mutex guard on channel internal for use in channel operations
 #[inline(always)]
-pub fn try_acquire_internal<T>(
-    internal: &'_ Internal<T>,
-) -> Option<MutexGuard<'_, ChannelInternal<T>>> {
+pub fn try_acquire_internal<T>(internal: &'_ Internal<T>) -> Option<InternalGuard<'_, T>> {
     #[cfg(not(feature = "std-mutex"))]
-    return internal.try_lock();
+    let guard = internal.mutex.try_lock()?;
     #[cfg(feature = "std-mutex")]
-    internal.try_lock().ok()
+    let guard = internal.mutex.try_lock().ok()?;
+    Some(InternalGuard {
+        guard,
+        snapshot: &internal.snapshot,
+    })
+}
+
+/// Returns the error of a receive operation whose signal is terminated, the receive side of the channel is still open
+///  if only its send side is closed, by `close_send` or by dropping all senders
+pub fn terminated_recv_error<T>(internal: &Internal<T>) -> ReceiveError {
+    if acquire_internal(internal).recv_count == 0 {
+        ReceiveError::Closed
+    } else {
+        ReceiveError::SendClosed
+    }
 }
 
 /// Internal of the channel that holds queues, waitlists, and general state of the channel,
@@ -35,120 +139,248 @@ pub fn try_acquire_internal<T>(
 pub struct ChannelInternal<T> {
     // KEEP THE ORDER
     /// Channel queue to save buffered objects
-    pub queue: VecDeque<T>,
+    pub queue: Queue<T>,
     /// Receive waitlist for when the channel queue is empty or zero capacity
-    pub recv_wait: VecDeque<Signal<T>>,
+    pub recv_wait: WaitList<T>,
     /// The sender waitlist for when the channel queue is full or zero capacity
-    pub send_wait: VecDeque<Signal<T>>,
+    pub send_wait: WaitList<T>,
     /// The capacity of the channel buffer
     pub capacity: usize,
     /// Count of alive receivers
     pub recv_count: u32,
     /// Count of alive senders
     pub send_count: u32,
+    /// Behavior of the channel when the queue is full
+    pub overflow: OverflowPolicy,
+    /// Count of objects that are evicted based on the overflow policy
+    pub evicted: u64,
+    /// Debug name of the channel
+    pub name: Option<Box<str>>,
+    /// Count of spins of sync waiters before they wait on the os
+    pub spins: usize,
+    /// Metrics of the channel, they are only collected if they are enabled on construction
+    pub metrics: Option<ChannelMetrics>,
 }
 
 impl<T> ChannelInternal<T> {
     /// Returns a channel internal with the required capacity
-    pub fn new(bounded: bool, capacity: usize) -> Internal<T> {
+    pub fn new(bounded: bool, capacity: usize) -> Self {
+        Self::with_queue(bounded, capacity, Queue::fifo(capacity))
+    }
+
+    /// Returns a channel internal with the required capacity that buffers objects in the provided queue,
+    ///  other options of the channel are set to their defaults
+    pub fn with_queue(bounded: bool, capacity: usize, queue: Queue<T>) -> Self {
         let mut abstract_capacity = capacity;
         if !bounded {
             // act like there is no limit
             abstract_capacity = usize::MAX;
         }
 
-        let ret = Self {
-            queue: VecDeque::with_capacity(capacity),
-            recv_wait: VecDeque::new(),
-            send_wait: VecDeque::new(),
+        Self {
+            queue,
+            recv_wait: WaitList::new(),
+            send_wait: WaitList::new(),
             recv_count: 1,
             send_count: 1,
             capacity: abstract_capacity,
+            overflow: OverflowPolicy::Block,
+            evicted: 0,
+            name: None,
+            spins: SPIN_COUNT,
+            metrics: None,
+        }
+    }
+
+    /// Moves the channel internal behind the shared mutex, so it can be used by senders and receivers
+    pub fn into_shared(self) -> Internal<T> {
+        let snapshot = Snapshot {
+            capacity: self.capacity,
+            len: AtomicUsize::new(self.queue.len()),
+            recv_count: AtomicU32::new(self.recv_count),
+            send_count: AtomicU32::new(self.send_count),
         };
+        Arc::new(SharedInternal {
+            mutex: Mutex::from(self),
+            snapshot,
+        })
+    }
+
+    /// Returns whether a sender can buffer its object instead of waiting on the waitlist,
+    ///  either the queue has room or the overflow policy evicts an object on a full queue
+    #[inline(always)]
+    pub fn can_buffer(&self) -> bool {
+        self.queue.len() < self.capacity || self.overflow != OverflowPolicy::Block
+    }
+
+    /// Buffers the object and returns the object that is evicted based on the overflow policy if the queue is full,
+    ///  callers should check `can_buffer` first and drop the evicted object outside of the lock
+    #[inline(always)]
+    pub fn buffer(&mut self, data: T) -> Option<T> {
+        if let Some(metrics) = &mut self.metrics {
+            metrics.sent += 1;
+        }
+        if self.queue.len() < self.capacity {
+            self.queue.push_back(data);
+            if let Some(metrics) = &mut self.metrics {
+                metrics.peak_len = metrics.peak_len.max(self.queue.len());
+            }
+            return None;
+        }
+        self.evicted += 1;
+        match self.overflow {
+            OverflowPolicy::DropOldest => {
+                // builders reject zero sized channels with an evicting policy, so a full queue has an oldest object
+                let oldest = self.queue.pop_oldest();
+                self.queue.push_back(data);
+                oldest
+            }
+            OverflowPolicy::DropNewest => Some(data),
+            OverflowPolicy::Block => {
+                unreachable!("bug: blocking channels should not evict objects")
+            }
+        }
+    }
+
+    /// Returns whether a receive operation can complete without waiting, either with an object or an error
+    pub fn recv_ready(&self) -> bool {
+        self.recv_count == 0
+            || !self.queue.is_empty()
+            || self.send_count == 0
+            || self.send_wait.has_waiter()
+    }
+
+    /// Returns whether a receive operation can only fail, the channel is closed or its send side is closed and drained
+    #[cfg(feature = "async")]
+    pub fn recv_closed(&self) -> bool {
+        self.recv_count == 0 || (self.send_count == 0 && self.queue.is_empty())
+    }
+
+    /// Returns whether a send operation can complete without waiting, either by moving the object or with an error
+    pub fn send_ready(&self) -> bool {
+        self.recv_count == 0 || self.can_buffer() || self.recv_wait.has_waiter()
+    }
+
+    /// Returns whether a sender that waits for room can start sending, receivers that only wait for a notification
+    ///  count as room too, as they take the object from the send waitlist once the sender notifies them
+    #[cfg(feature = "async")]
+    pub fn send_room(&self) -> bool {
+        self.send_ready() || self.recv_wait.len() > 0
+    }
 
-        Arc::new(Mutex::from(ret))
+    /// Returns a snapshot of the channel metrics if they are enabled
+    pub fn metrics_snapshot(&self) -> Option<ChannelMetrics> {
+        let mut metrics = self.metrics.clone()?;
+        metrics.evicted = self.evicted;
+        metrics.len = self.queue.len();
+        metrics.waiting_senders = self.send_wait.len();
+        metrics.waiting_receivers = self.recv_wait.len();
+        Some(metrics)
     }
 
     /// Terminates remainings signals in the queue to notify listeners about the closing of the channel
     pub fn terminate_signals(&mut self) {
-        for v in &self.send_wait {
+        // signals are unlinked before the termination, as their waiters may release them right after it
+        while let Some(v) = self.send_wait.pop_front() {
             // Safety: it's safe to terminate owned signal once
             unsafe { v.terminate() }
         }
-        self.send_wait.clear();
-        for v in &self.recv_wait {
+        while let Some(v) = self.recv_wait.pop_front() {
             // Safety: it's safe to terminate owned signal once
             unsafe { v.terminate() }
         }
-        self.recv_wait.clear();
     }
 
     /// Returns next signal for sender from the waitlist
     #[inline(always)]
     pub fn next_send(&mut self) -> Option<Signal<T>> {
-        self.send_wait.pop_front()
+        while let Some(sig) = self.send_wait.pop_front() {
+            if sig.is_select() {
+                // Safety: select signals are released by their owner only after they are removed from waitlists under the lock
+                unsafe { sig.notify() }
+                continue;
+            }
+            if let Some(metrics) = &mut self.metrics {
+                metrics.sent += 1;
+            }
+            return Some(sig);
+        }
+        None
     }
 
     /// Adds new sender signal to the waitlist
     #[inline(always)]
     pub fn push_send(&mut self, s: Signal<T>) {
-        self.send_wait.push_back(s);
+        // Safety: waiters keep their signals in place until they are removed from the waitlist
+        unsafe { self.send_wait.push_back(s) }
     }
 
     /// Returns the next signal for the receiver in the waitlist
     #[inline(always)]
     pub fn next_recv(&mut self) -> Option<Signal<T>> {
-        self.recv_wait.pop_front()
+        while let Some(sig) = self.recv_wait.pop_front() {
+            if sig.is_select() {
+                // Safety: select signals are released by their owner only after they are removed from waitlists under the lock
+                unsafe { sig.notify() }
+                continue;
+            }
+            if let Some(metrics) = &mut self.metrics {
+                metrics.sent += 1;
+            }
+            return Some(sig);
+        }
+        None
+    }
+
+    /// Notifies select signals at the front of the receive waitlist and returns whether a receiver is waiting,
+    ///  the receiver stays in the waitlist until it's taken with `next_recv`
+    pub fn has_recv_waiter(&mut self) -> bool {
+        while let Some(sig) = self.recv_wait.front() {
+            if !sig.is_select() {
+                return true;
+            }
+            self.recv_wait.pop_front();
+            // Safety: select signals are released by their owner only after they are removed from waitlists under the lock
+            unsafe { sig.notify() }
+        }
+        false
     }
 
     /// Adds new receiver signal to the waitlist
     #[inline(always)]
     pub fn push_recv(&mut self, s: Signal<T>) {
-        self.recv_wait.push_back(s);
+        // Safety: waiters keep their signals in place until they are removed from the waitlist
+        unsafe { self.recv_wait.push_back(s) }
     }
 
     /// Tries to remove the send signal from the waitlist, returns true if the operation was successful
+    #[inline(always)]
     pub fn cancel_send_signal(&mut self, sig: Signal<T>) -> bool {
-        for (i, send) in self.send_wait.iter().enumerate() {
-            if sig == *send {
-                self.send_wait.remove(i);
-                return true;
-            }
-        }
-        false
+        // Safety: the owner of the signal cancels it, and send signals are only linked in the send waitlist
+        unsafe { self.send_wait.remove(sig) }
     }
 
     /// Tries to remove the received signal from the waitlist, returns true if the operation was successful
+    #[inline(always)]
     pub fn cancel_recv_signal(&mut self, sig: Signal<T>) -> bool {
-        for (i, recv) in self.recv_wait.iter().enumerate() {
-            if sig == *recv {
-                self.recv_wait.remove(i);
-                return true;
-            }
-        }
-        false
+        // Safety: the owner of the signal cancels it, and receive signals are only linked in the receive waitlist
+        unsafe { self.recv_wait.remove(sig) }
     }
 
     /// checks if send signal exists in wait list
     #[cfg(feature = "async")]
-    pub fn send_signal_exists(&mut self, sig: Signal<T>) -> bool {
-        for signal in self.send_wait.iter() {
-            if sig == *signal {
-                return true;
-            }
-        }
-        false
+    #[inline(always)]
+    pub fn send_signal_exists(&self, sig: Signal<T>) -> bool {
+        // Safety: the owner of the signal checks it, and send signals are only linked in the send waitlist
+        unsafe { self.send_wait.contains(sig) }
     }
 
     /// checks if receive signal exists in wait list
     #[cfg(feature = "async")]
-    pub fn recv_signal_exists(&mut self, sig: Signal<T>) -> bool {
-        for signal in self.recv_wait.iter() {
-            if sig == *signal {
-                return true;
-            }
-        }
-        false
+    #[inline(always)]
+    pub fn recv_signal_exists(&self, sig: Signal<T>) -> bool {
+        // Safety: the owner of the signal checks it, and receive signals are only linked in the receive waitlist
+        unsafe { self.recv_wait.contains(sig) }
     }
 }
 
@@ -19,12 +19,39 @@ pub(crate) mod pointer;
 mod error;
 pub use error::*;
 
+mod oneshot;
+pub use oneshot::*;
+
+mod broadcast;
+pub use broadcast::*;
+
+mod watch;
+pub use watch::*;
+
 pub(crate) mod internal;
 pub(crate) mod mutex;
+mod queue;
+pub use queue::{OverflowPolicy, PriorityOrder};
+
+mod builder;
+pub use builder::{Builder, ChannelMetrics};
+mod select;
+#[cfg(feature = "async")]
+pub use select::recv_any;
+pub use select::{Select, SelectedOperation};
+mod weak;
+#[cfg(feature = "async")]
+pub use weak::{WeakAsyncReceiver, WeakAsyncSender};
+pub use weak::{WeakReceiver, WeakSender};
 mod signal;
 pub(crate) mod state;
+#[cfg(feature = "async")]
+pub(crate) mod timer;
+mod waitlist;
 
-use internal::{acquire_internal, try_acquire_internal, ChannelInternal, Internal};
+use internal::{
+    acquire_internal, terminated_recv_error, try_acquire_internal, ChannelInternal, Internal,
+};
 use pointer::KanalPtr;
 
 use std::mem::{forget, needs_drop, size_of, MaybeUninit};
@@ -34,6 +61,8 @@ use std::fmt;
 use std::fmt::Debug;
 
 use signal::*;
+#[cfg(feature = "async")]
+use timer::Delay;
 
 /// Sending side of the channel in sync mode.
 /// Senders can be cloned and produce senders to operate in both sync and async modes.
@@ -59,6 +88,8 @@ pub struct Sender<T> {
 #[cfg(feature = "async")]
 pub struct AsyncSender<T> {
     internal: Internal<T>,
+    /// Waiter slot of `poll_send_ready` and `start_send`
+    slot: PollSlot<T>,
 }
 
 impl<T> Drop for Sender<T> {
@@ -76,6 +107,8 @@ impl<T> Drop for Sender<T> {
 #[cfg(feature = "async")]
 impl<T> Drop for AsyncSender<T> {
     fn drop(&mut self) {
+        // the object that is still waiting in the slot is dropped with the sender
+        self.slot.cancel_send(&self.internal);
         let mut internal = acquire_internal(&self.internal);
         if internal.send_count > 0 {
             internal.send_count -= 1;
@@ -92,15 +125,48 @@ impl<T> Clone for Sender<T> {
         if internal.send_count > 0 {
             internal.send_count += 1;
         }
-        Self {
-            internal: self.internal.clone(),
-        }
+        Self::new(self.internal.clone())
+    }
+}
+
+/// Sends objects of the iterator to an unbounded channel with [`Sender::send_many`], sending stops silently
+///  if the channel is closed, use [`Sender::send_many`] to handle closed channels.
+/// # Panics
+///
+/// Panics if the channel is bounded, as extending a channel never waits for room.
+/// # Examples
+///
+/// ```
+/// let (mut s, r) = kanal::unbounded();
+/// s.extend(vec![1, 2, 3]);
+/// assert_eq!(r.len(), 3);
+/// ```
+impl<T> Extend<T> for Sender<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        assert!(
+            self.capacity() == usize::MAX,
+            "extend is only supported on unbounded channels"
+        );
+        // objects that are not sent to a closed channel are dropped along with the rest of the iterator
+        let _ = self.send_many(iter);
+    }
+}
+
+/// Formats a channel endpoint with the debug name of its channel if it has one
+fn debug_channel<T>(
+    f: &mut fmt::Formatter<'_>,
+    endpoint: &str,
+    internal: &Internal<T>,
+) -> fmt::Result {
+    match &acquire_internal(internal).name {
+        Some(name) => write!(f, "{} {{ name: {:?}, .. }}", endpoint, name),
+        None => write!(f, "{} {{ .. }}", endpoint),
     }
 }
 
 impl<T> Debug for Sender<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Sender {{ .. }}")
+        debug_channel(f, "Sender", &self.internal)
     }
 }
 
@@ -111,16 +177,14 @@ impl<T> Clone for AsyncSender<T> {
         if internal.send_count > 0 {
             internal.send_count += 1;
         }
-        Self {
-            internal: self.internal.clone(),
-        }
+        Self::new(self.internal.clone())
     }
 }
 
 #[cfg(feature = "async")]
 impl<T> Debug for AsyncSender<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "AsyncSender {{ .. }}")
+        debug_channel(f, "AsyncSender", &self.internal)
     }
 }
 
@@ -140,9 +204,12 @@ macro_rules! shared_impl {
         /// assert_eq!(r.is_bounded(),false);
         /// ```
         pub fn is_bounded(&self) -> bool {
-            acquire_internal(&self.internal).capacity != usize::MAX
+            self.internal.snapshot.capacity != usize::MAX
         }
-        /// Returns length of the queue
+        /// Returns length of the queue.
+        /// State queries like this one read a snapshot of the channel that is published on each operation instead of
+        ///  acquiring the channel mutex, so they don't contend with channel operations, and the result may be outdated
+        ///  right away if other endpoints operate concurrently.
         /// # Examples
         ///
         /// ```
@@ -154,7 +221,7 @@ macro_rules! shared_impl {
         /// assert_eq!(r.len(),1);
         /// ```
         pub fn len(&self) -> usize {
-            acquire_internal(&self.internal).queue.len()
+            self.internal.snapshot.len()
         }
         /// Returns whether the channel queue is empty or not
         /// # Examples
@@ -165,7 +232,7 @@ macro_rules! shared_impl {
         /// assert_eq!(r.is_empty(),true);
         /// ```
         pub fn is_empty(&self) -> bool {
-            acquire_internal(&self.internal).queue.is_empty()
+            self.internal.snapshot.len() == 0
         }
         /// Returns whether the channel queue is full or not
         /// full channels will block on send and recv calls
@@ -179,8 +246,8 @@ macro_rules! shared_impl {
         /// assert_eq!(r.is_full(),true);
         /// ```
         pub fn is_full(&self) -> bool {
-            let internal = acquire_internal(&self.internal);
-            internal.capacity == internal.queue.len()
+            let snapshot = &self.internal.snapshot;
+            snapshot.capacity == snapshot.len()
         }
         /// Returns capacity of channel (not the queue)
         /// for unbounded channels, it will return usize::MAX
@@ -197,7 +264,39 @@ macro_rules! shared_impl {
         /// assert_eq!(r.capacity(),usize::MAX);
         /// ```
         pub fn capacity(&self) -> usize {
-            acquire_internal(&self.internal).capacity
+            self.internal.snapshot.capacity
+        }
+        /// Returns count of objects that are evicted based on the overflow policy of the channel
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::bounded_with_overflow(1, kanal::OverflowPolicy::DropNewest);
+        /// s.send(1)?;
+        /// s.send(2)?;
+        /// assert_eq!(s.evicted_count(),1);
+        /// assert_eq!(r.evicted_count(),1);
+        /// # anyhow::Ok(())
+        /// ```
+        pub fn evicted_count(&self) -> u64 {
+            acquire_internal(&self.internal).evicted
+        }
+        /// Returns a snapshot of the channel metrics, or `None` if metrics are not enabled by the [`Builder`]
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::Builder::new().bounded(4).metrics(true).build();
+        /// s.send(1)?;
+        /// s.send(2)?;
+        /// r.recv()?;
+        /// let metrics = r.metrics().unwrap();
+        /// assert_eq!(metrics.sent, 2);
+        /// assert_eq!(metrics.len, 1);
+        /// assert_eq!(metrics.peak_len, 2);
+        /// assert_eq!(kanal::unbounded::<u64>().0.metrics(), None);
+        /// # anyhow::Ok(())
+        /// ```
+        pub fn metrics(&self) -> Option<ChannelMetrics> {
+            acquire_internal(&self.internal).metrics_snapshot()
         }
         /// Returns count of alive receiver instances of the channel
         /// # Examples
@@ -208,7 +307,7 @@ macro_rules! shared_impl {
         /// assert_eq!(r.receiver_count(),2);
         /// ```
         pub fn receiver_count(&self) -> u32 {
-            acquire_internal(&self.internal).recv_count
+            self.internal.snapshot.recv_count()
         }
         /// Returns count of alive sender instances of the channel
         /// # Examples
@@ -219,7 +318,7 @@ macro_rules! shared_impl {
         /// assert_eq!(r.sender_count(),2);
         /// ```
         pub fn sender_count(&self) -> u32 {
-            acquire_internal(&self.internal).send_count
+            self.internal.snapshot.send_count()
         }
         /// Closes the channel completely on both sides and terminates waiting signals
         /// # Examples
@@ -239,9 +338,54 @@ macro_rules! shared_impl {
             internal.recv_count = 0;
             internal.send_count = 0;
             internal.terminate_signals();
-            internal.queue.clear();
+            let queue = internal.queue.take();
+            drop(internal);
+            // buffered objects are dropped outside of the lock
+            drop(queue);
             true
         }
+        /// Closes the channel completely on both sides like [`close`](Self::close), and hands back the undelivered objects
+        ///  instead of dropping them: buffered objects in the receive order, followed by objects of the senders that are
+        ///  waiting on the waitlist, which complete their send successfully.
+        /// It returns an empty vector if the channel is already closed.
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::unbounded();
+        /// s.send(1)?;
+        /// s.send(2)?;
+        /// assert_eq!(r.close_and_drain(), vec![1, 2]);
+        /// assert!(s.is_closed());
+        /// # anyhow::Ok(())
+        /// ```
+        pub fn close_and_drain(&self) -> Vec<T> {
+            let mut internal = acquire_internal(&self.internal);
+            if internal.recv_count == 0 && internal.send_count == 0 {
+                return Vec::new();
+            }
+            internal.recv_count = 0;
+            internal.send_count = 0;
+            let mut queue = internal.queue.take();
+            let mut senders = Vec::new();
+            let mut handoffs = Vec::new();
+            while let Some(p) = internal.next_send() {
+                // Safety: it's safe to receive from owned signal once
+                let (data, handoff) = unsafe { p.recv_locked() };
+                senders.push(data);
+                handoffs.push(handoff);
+            }
+            internal.terminate_signals();
+            drop(internal);
+            for handoff in handoffs {
+                handoff.finish();
+            }
+            let mut items = Vec::with_capacity(queue.len() + senders.len());
+            while let Some(data) = queue.pop_front() {
+                items.push(data);
+            }
+            items.extend(senders);
+            items
+        }
         /// Returns whether the channel is closed on both side of send and receive or not
         /// # Examples
         ///
@@ -253,16 +397,41 @@ macro_rules! shared_impl {
         /// assert_eq!(s.is_closed(),true);
         /// ```
         pub fn is_closed(&self) -> bool {
-            let internal = acquire_internal(&self.internal);
-            internal.send_count == 0 && internal.recv_count == 0
+            let snapshot = &self.internal.snapshot;
+            snapshot.send_count() == 0 && snapshot.recv_count() == 0
         }
     };
 }
 
+/// Moves the object of a failed try send back into the option for the deprecated `_option` send variants,
+///  a full or contended channel is reported as `Ok(false)`.
+#[inline(always)]
+fn try_send_option_result<T>(
+    data: &mut Option<T>,
+    res: Result<(), TrySendError<T>>,
+) -> Result<bool, SendError<()>> {
+    match res {
+        Ok(()) => Ok(true),
+        Err(TrySendError::Full(v) | TrySendError::Contended(v)) => {
+            *data = Some(v);
+            Ok(false)
+        }
+        Err(TrySendError::Closed(v)) => {
+            *data = Some(v);
+            Err(SendError::Closed(()))
+        }
+        Err(TrySendError::ReceiveClosed(v)) => {
+            *data = Some(v);
+            Err(SendError::ReceiveClosed(()))
+        }
+    }
+}
+
 macro_rules! shared_send_impl {
     () => {
-        /// Tries sending to the channel without waiting on the waitlist, if send fails then the object will be dropped.
-        /// It returns `Ok(true)` in case of a successful operation and `Ok(false)` for a failed one, or error in case that channel is closed.
+        /// Tries sending to the channel without waiting on the waitlist.
+        /// It returns `Ok(())` in case of a successful operation, `TrySendError::Full` if the channel is full or an error in case that channel is closed,
+        ///  the object is handed back inside the error on failure.
         /// Important note: this function is not lock-free as it acquires a mutex guard of the channel internal for a short time.
         /// # Examples
         ///
@@ -270,9 +439,12 @@ macro_rules! shared_send_impl {
         /// # use std::thread::spawn;
         /// let (s, r) = kanal::bounded(0);
         /// let t=spawn( move || {
+        ///     let mut data=1;
         ///     loop{
-        ///         if s.try_send(1).unwrap(){
-        ///             break;
+        ///         match s.try_send(data){
+        ///             Ok(()) => break,
+        ///             Err(kanal::TrySendError::Full(v)) => data=v,
+        ///             Err(err) => panic!("{}",err),
         ///         }
         ///     }
         /// });
@@ -281,35 +453,41 @@ macro_rules! shared_send_impl {
         /// # anyhow::Ok(())
         /// ```
         #[inline(always)]
-        pub fn try_send(&self, data: T) -> Result<bool, SendError> {
+        pub fn try_send(&self, data: T) -> Result<(), TrySendError<T>> {
             let mut internal = acquire_internal(&self.internal);
-            if internal.recv_count == 0 {
+            if internal.recv_count == 0 || internal.send_count == 0 {
                 let send_count = internal.send_count;
-                // Avoid wasting lock time on dropping failed send object
                 drop(internal);
                 if send_count == 0 {
-                    return Err(SendError::Closed);
+                    return Err(TrySendError::Closed(data));
                 }
-                return Err(SendError::ReceiveClosed);
+                return Err(TrySendError::ReceiveClosed(data));
             }
             if let Some(first) = internal.next_recv() {
-                drop(internal);
                 // Safety: it's safe to send to owned signal once
-                unsafe { first.send(data) }
-                return Ok(true);
-            } else if internal.queue.len() < internal.capacity {
-                internal.queue.push_back(data);
-                return Ok(true);
+                let handoff = unsafe { first.send_locked(data) };
+                drop(internal);
+                handoff.finish();
+                return Ok(());
+            } else if internal.can_buffer() {
+                let evicted = internal.buffer(data);
+                drop(internal);
+                // drop the evicted object outside of the lock
+                drop(evicted);
+                return Ok(());
             }
-            Ok(false)
+            drop(internal);
+            Err(TrySendError::Full(data))
         }
 
-        /// Tries sending to the channel without waiting on the waitlist, if send fails then the object will be dropped.
+        /// Tries sending the object of the option to the channel without waiting on the waitlist, the object is left in the option on failure.
         /// It returns `Ok(true)` in case of a successful operation and `Ok(false)` for a failed one, or error in case that channel is closed.
+        /// This function will `panic` if the option is `None`.
         /// Important note: this function is not lock-free as it acquires a mutex guard of the channel internal for a short time.
         /// # Examples
         ///
         /// ```
+        /// # #![allow(deprecated)]
         /// # use std::thread::spawn;
         /// let (s, r) = kanal::bounded(0);
         /// let t=spawn( move || {
@@ -324,32 +502,111 @@ macro_rules! shared_send_impl {
         /// # t.join();
         /// # anyhow::Ok(())
         /// ```
+        #[deprecated(note = "use `try_send`, its error hands the object back")]
         #[inline(always)]
-        pub fn try_send_option(&self, data: &mut Option<T>) -> Result<bool, SendError> {
+        pub fn try_send_option(&self, data: &mut Option<T>) -> Result<bool, SendError<()>> {
+            let res = self.try_send(data.take().unwrap());
+            try_send_option_result(data, res)
+        }
+
+        /// Tries sending to the channel without waiting on the waitlist, and hands back the object that is evicted
+        ///  based on the overflow policy of the channel.
+        /// It returns `Ok(None)` if no object is evicted, `Ok(Some(evicted))` if the channel was full, `TrySendError::Full` if the channel is full
+        ///  and its overflow policy is `Block`, or an error in case that channel is closed.
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::bounded_with_overflow(1, kanal::OverflowPolicy::DropNewest);
+        /// assert_eq!(s.try_send_evicting(1)?, None);
+        /// assert_eq!(s.try_send_evicting(2)?, Some(2));
+        /// assert_eq!(r.recv()?, 1);
+        /// # anyhow::Ok(())
+        /// ```
+        pub fn try_send_evicting(&self, data: T) -> Result<Option<T>, TrySendError<T>> {
             let mut internal = acquire_internal(&self.internal);
-            if internal.recv_count == 0 {
+            if internal.recv_count == 0 || internal.send_count == 0 {
                 let send_count = internal.send_count;
-                // Avoid wasting lock time on dropping failed send object
                 drop(internal);
                 if send_count == 0 {
-                    return Err(SendError::Closed);
+                    return Err(TrySendError::Closed(data));
                 }
-                return Err(SendError::ReceiveClosed);
+                return Err(TrySendError::ReceiveClosed(data));
             }
             if let Some(first) = internal.next_recv() {
-                drop(internal);
                 // Safety: it's safe to send to owned signal once
-                unsafe { first.send(data.take().unwrap()) }
-                return Ok(true);
-            } else if internal.queue.len() < internal.capacity {
-                internal.queue.push_back(data.take().unwrap());
-                return Ok(true);
+                let handoff = unsafe { first.send_locked(data) };
+                drop(internal);
+                handoff.finish();
+                return Ok(None);
+            } else if internal.can_buffer() {
+                return Ok(internal.buffer(data));
             }
-            Ok(false)
+            drop(internal);
+            Err(TrySendError::Full(data))
         }
 
-        /// Tries sending to the channel without waiting on the waitlist or for the internal mutex, if send fails then the object will be dropped.
-        /// It returns `Ok(true)` in case of a successful operation and `Ok(false)` for a failed one, or error in case that channel is closed.
+        /// Tries sending objects of the iterator to the channel without waiting on the waitlist, objects are handed to waiting receivers
+        ///  and buffered in the queue under a single lock of the channel.
+        /// It returns `Ok(count)` with the count of accepted objects, which is less than the count of objects if the channel got full,
+        ///  or an error with the count of accepted objects in case that channel is closed, the error holds no object as none is taken from the iterator.
+        /// The iterator is only advanced when the channel has room for the next object, so objects that are not accepted
+        ///  stay in the iterator if it's passed by reference. It's advanced while the lock is held, so it should be cheap.
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::bounded(2);
+        /// let mut items = 1..=3;
+        /// assert_eq!(s.try_send_many(&mut items)?, 2);
+        /// assert_eq!(items.next(), Some(3));
+        /// assert_eq!(r.len(), 2);
+        /// # anyhow::Ok(())
+        /// ```
+        pub fn try_send_many<I: IntoIterator<Item = T>>(
+            &self,
+            items: I,
+        ) -> Result<usize, SendManyError<T>> {
+            let mut items = items.into_iter();
+            let mut internal = acquire_internal(&self.internal);
+            if internal.recv_count == 0 || internal.send_count == 0 {
+                let send_count = internal.send_count;
+                drop(internal);
+                if send_count == 0 {
+                    return Err(SendManyError::Closed(0, None));
+                }
+                return Err(SendManyError::ReceiveClosed(0, None));
+            }
+            let mut count = 0;
+            // waiters are woken and evicted objects are dropped outside of the lock
+            let mut handoffs = Vec::new();
+            let mut evicted = Vec::new();
+            loop {
+                let waiting = internal.has_recv_waiter();
+                if !waiting && !internal.can_buffer() {
+                    break;
+                }
+                let data = match items.next() {
+                    Some(data) => data,
+                    None => break,
+                };
+                if waiting {
+                    // Safety: it's safe to send to owned signal once
+                    handoffs.push(unsafe { internal.next_recv().unwrap().send_locked(data) });
+                } else {
+                    evicted.extend(internal.buffer(data));
+                }
+                count += 1;
+            }
+            drop(internal);
+            for handoff in handoffs {
+                handoff.finish();
+            }
+            drop(evicted);
+            Ok(count)
+        }
+
+        /// Tries sending to the channel without waiting on the waitlist or for the internal mutex.
+        /// It returns `Ok(())` in case of a successful operation, `TrySendError::Full` if the channel is full, `TrySendError::Contended` if its internal mutex is locked,
+        ///  or an error in case that channel is closed, the object is handed back inside the error on failure.
         /// Do not use this function unless you know exactly what you are doing.
         /// # Examples
         ///
@@ -357,9 +614,12 @@ macro_rules! shared_send_impl {
         /// # use std::thread::spawn;
         /// let (s, r) = kanal::bounded(0);
         /// let t=spawn( move || {
+        ///     let mut data=1;
         ///     loop{
-        ///         if s.try_send_realtime(1).unwrap(){
-        ///             break;
+        ///         match s.try_send_realtime(data){
+        ///             Ok(()) => break,
+        ///             Err(kanal::TrySendError::Full(v) | kanal::TrySendError::Contended(v)) => data=v,
+        ///             Err(err) => panic!("{}",err),
         ///         }
         ///     }
         /// });
@@ -368,37 +628,44 @@ macro_rules! shared_send_impl {
         /// # anyhow::Ok(())
         /// ```
         #[inline(always)]
-        pub fn try_send_realtime(&self, data: T) -> Result<bool, SendError> {
+        pub fn try_send_realtime(&self, data: T) -> Result<(), TrySendError<T>> {
             if let Some(mut internal) = try_acquire_internal(&self.internal) {
-                if internal.recv_count == 0 {
+                if internal.recv_count == 0 || internal.send_count == 0 {
                     let send_count = internal.send_count;
-                    // Avoid wasting lock time on dropping failed send object
                     drop(internal);
                     if send_count == 0 {
-                        return Err(SendError::Closed);
+                        return Err(TrySendError::Closed(data));
                     }
-                    return Err(SendError::ReceiveClosed);
+                    return Err(TrySendError::ReceiveClosed(data));
                 }
                 if let Some(first) = internal.next_recv() {
-                    drop(internal);
                     // Safety: it's safe to send to owned signal once
-                    unsafe { first.send(data) }
-                    return Ok(true);
-                } else if internal.queue.len() < internal.capacity {
-                    internal.queue.push_back(data);
-                    return Ok(true);
+                    let handoff = unsafe { first.send_locked(data) };
+                    drop(internal);
+                    handoff.finish();
+                    return Ok(());
+                } else if internal.can_buffer() {
+                    let evicted = internal.buffer(data);
+                    drop(internal);
+                    // drop the evicted object outside of the lock
+                    drop(evicted);
+                    return Ok(());
                 }
+                drop(internal);
+                return Err(TrySendError::Full(data));
             }
-            Ok(false)
+            Err(TrySendError::Contended(data))
         }
 
-        /// Tries sending to the channel without waiting on the waitlist or channel internal lock.
+        /// Tries sending the object of the option to the channel without waiting on the waitlist or channel internal lock,
+        ///  the object is left in the option on failure.
         /// It returns `Ok(true)` in case of a successful operation and `Ok(false)` for a failed one, or error in case that channel is closed.
-        /// This function will `panic` on successfull send attempt of `None` data.
+        /// This function will `panic` if the option is `None`.
         /// Do not use this function unless you know exactly what you are doing.
         /// # Examples
         ///
         /// ```
+        /// # #![allow(deprecated)]
         /// # use std::thread::spawn;
         /// let (s, r) = kanal::bounded(0);
         /// let t=spawn( move || {
@@ -413,29 +680,40 @@ macro_rules! shared_send_impl {
         /// # t.join();
         /// # anyhow::Ok(())
         /// ```
+        #[deprecated(note = "use `try_send_realtime`, its error hands the object back")]
         #[inline(always)]
-        pub fn try_send_option_realtime(&self, data: &mut Option<T>) -> Result<bool, SendError> {
-            if let Some(mut internal) = try_acquire_internal(&self.internal) {
-                if internal.recv_count == 0 {
-                    let send_count = internal.send_count;
-                    // Avoid wasting lock time on dropping failed send object
-                    drop(internal);
-                    if send_count == 0 {
-                        return Err(SendError::Closed);
-                    }
-                    return Err(SendError::ReceiveClosed);
-                }
-                if let Some(first) = internal.next_recv() {
-                    drop(internal);
-                    // Safety: it's safe to send to owned signal once
-                    unsafe { first.send(data.take().unwrap()) }
-                    return Ok(true);
-                } else if internal.queue.len() < internal.capacity {
-                    internal.queue.push_back(data.take().unwrap());
-                    return Ok(true);
-                }
+        pub fn try_send_option_realtime(
+            &self,
+            data: &mut Option<T>,
+        ) -> Result<bool, SendError<()>> {
+            let res = self.try_send_realtime(data.take().unwrap());
+            try_send_option_result(data, res)
+        }
+
+        /// Closes the send side of the channel gracefully, new sends fail with a closed error even from other senders of the channel,
+        ///  and senders that are waiting on the waitlist are released with their objects inside the error.
+        /// Receivers can still receive objects that are already in the queue, and they get `ReceiveError::SendClosed` after that.
+        /// It returns false if the send side is already closed.
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::unbounded();
+        /// let other = s.clone();
+        /// s.send(1)?;
+        /// assert!(s.close_send());
+        /// assert_eq!(other.send(2), Err(kanal::SendError::Closed(2)));
+        /// assert_eq!(r.recv()?, 1);
+        /// assert_eq!(r.recv(), Err(kanal::ReceiveError::SendClosed));
+        /// # anyhow::Ok(())
+        /// ```
+        pub fn close_send(&self) -> bool {
+            let mut internal = acquire_internal(&self.internal);
+            if internal.send_count == 0 {
+                return false;
             }
-            Ok(false)
+            internal.send_count = 0;
+            internal.terminate_signals();
+            true
         }
 
         /// Returns whether the receive side of the channel is closed or not
@@ -448,7 +726,7 @@ macro_rules! shared_send_impl {
         /// # anyhow::Ok(())
         /// ```
         pub fn is_disconnected(&self) -> bool {
-            acquire_internal(&self.internal).recv_count == 0
+            self.internal.snapshot.recv_count() == 0
         }
     };
 }
@@ -456,7 +734,7 @@ macro_rules! shared_send_impl {
 macro_rules! shared_recv_impl {
     () => {
         /// Tries receiving from the channel without waiting on the waitlist.
-        /// It returns `Ok(Some(T))` in case of successful operation and `Ok(None)` for a failed one, or error in case that channel is closed.
+        /// It returns `Ok(T)` in case of successful operation, `TryRecvError::Empty` if there is no object to receive, or error in case that channel is closed.
         /// Important note: this function is not lock-free as it acquires a mutex guard of the channel internal for a short time.
         /// # Examples
         ///
@@ -468,19 +746,23 @@ macro_rules! shared_recv_impl {
         /// #      anyhow::Ok(())
         /// # });
         /// loop {
-        ///     if let Some(name)=r.try_recv()?{
-        ///         println!("Hello {}!",name);
-        ///         break;
+        ///     match r.try_recv(){
+        ///         Ok(name) => {
+        ///             println!("Hello {}!",name);
+        ///             break;
+        ///         }
+        ///         Err(kanal::TryRecvError::Empty) => continue,
+        ///         Err(err) => return Err(err.into()),
         ///     }
         /// }
         /// # t.join();
         /// # anyhow::Ok(())
         /// ```
         #[inline(always)]
-        pub fn try_recv(&self) -> Result<Option<T>, ReceiveError> {
+        pub fn try_recv(&self) -> Result<T, TryRecvError> {
             let mut internal = acquire_internal(&self.internal);
             if internal.recv_count == 0 {
-                return Err(ReceiveError::Closed);
+                return Err(TryRecvError::Closed);
             }
             if let Some(v) = internal.queue.pop_front() {
                 if let Some(p) = internal.next_send() {
@@ -488,20 +770,85 @@ macro_rules! shared_recv_impl {
                     // Safety: it's safe to receive from owned signal once
                     unsafe { internal.queue.push_back(p.recv()) }
                 }
-                return Ok(Some(v));
+                return Ok(v);
             } else if let Some(p) = internal.next_send() {
                 // Safety: it's safe to receive from owned signal once
+                let (data, handoff) = unsafe { p.recv_locked() };
                 drop(internal);
-                return unsafe { Ok(Some(p.recv())) };
+                handoff.finish();
+                return Ok(data);
             }
             if internal.send_count == 0 {
-                return Err(ReceiveError::SendClosed);
+                return Err(TryRecvError::SendClosed);
+            }
+            Err(TryRecvError::Empty)
+        }
+        /// Tries receiving up to `max` objects from the channel without waiting on the waitlist, objects are moved from the queue
+        ///  and from waiting senders to the end of the vector under a single lock of the channel.
+        /// It returns `Ok(count)` with the count of received objects, `TryRecvError::Empty` if there is no object to receive,
+        ///  or an error in case that channel is closed and there is no object left.
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::unbounded();
+        /// s.send_many(1..=5)?;
+        /// let mut buf = Vec::new();
+        /// assert_eq!(r.try_recv_many(&mut buf, 3)?, 3);
+        /// assert_eq!(r.try_recv_many(&mut buf, 3)?, 2);
+        /// assert_eq!(buf, vec![1, 2, 3, 4, 5]);
+        /// assert_eq!(r.try_recv_many(&mut buf, 3), Err(kanal::TryRecvError::Empty));
+        /// # anyhow::Ok(())
+        /// ```
+        pub fn try_recv_many(&self, buf: &mut Vec<T>, max: usize) -> Result<usize, TryRecvError> {
+            let mut internal = acquire_internal(&self.internal);
+            if internal.recv_count == 0 {
+                return Err(TryRecvError::Closed);
             }
-            Ok(None)
-            // if the queue is not empty send the data
+            let mut count = 0;
+            while count < max {
+                match internal.queue.pop_front() {
+                    Some(v) => {
+                        buf.push(v);
+                        count += 1;
+                        if let Some(p) = internal.next_send() {
+                            // if there is a sender take its data and push it into the queue
+                            // Safety: it's safe to receive from owned signal once
+                            unsafe { internal.queue.push_back(p.recv()) }
+                        }
+                    }
+                    None => break,
+                }
+            }
+            // senders of zero capacity channels hand their objects directly, they are woken outside of the lock
+            let mut handoffs = Vec::new();
+            while count < max {
+                match internal.next_send() {
+                    Some(p) => {
+                        // Safety: it's safe to receive from owned signal once
+                        let (data, handoff) = unsafe { p.recv_locked() };
+                        buf.push(data);
+                        handoffs.push(handoff);
+                        count += 1;
+                    }
+                    None => break,
+                }
+            }
+            let send_count = internal.send_count;
+            drop(internal);
+            for handoff in handoffs {
+                handoff.finish();
+            }
+            if count == 0 && max > 0 {
+                if send_count == 0 {
+                    return Err(TryRecvError::SendClosed);
+                }
+                return Err(TryRecvError::Empty);
+            }
+            Ok(count)
         }
         /// Tries receiving from the channel without waiting on the waitlist or waiting for channel internal lock.
-        /// It returns `Ok(Some(T))` in case of successful operation and `Ok(None)` for a failed one, or error in case that channel is closed.
+        /// It returns `Ok(T)` in case of successful operation, `TryRecvError::Empty` if there is no object to receive,
+        ///  `TryRecvError::Contended` if the channel internal lock is held by another operation, or error in case that channel is closed.
         /// Do not use this function unless you know exactly what you are doing.
         /// # Examples
         ///
@@ -513,19 +860,23 @@ macro_rules! shared_recv_impl {
         /// #      anyhow::Ok(())
         /// # });
         /// loop {
-        ///     if let Some(name)=r.try_recv_realtime()?{
-        ///         println!("Hello {}!",name);
-        ///         break;
+        ///     match r.try_recv_realtime(){
+        ///         Ok(name) => {
+        ///             println!("Hello {}!",name);
+        ///             break;
+        ///         }
+        ///         Err(kanal::TryRecvError::Empty | kanal::TryRecvError::Contended) => continue,
+        ///         Err(err) => return Err(err.into()),
         ///     }
         /// }
         /// # t.join();
         /// # anyhow::Ok(())
         /// ```
         #[inline(always)]
-        pub fn try_recv_realtime(&self) -> Result<Option<T>, ReceiveError> {
+        pub fn try_recv_realtime(&self) -> Result<T, TryRecvError> {
             if let Some(mut internal) = try_acquire_internal(&self.internal) {
                 if internal.recv_count == 0 {
-                    return Err(ReceiveError::Closed);
+                    return Err(TryRecvError::Closed);
                 }
                 if let Some(v) = internal.queue.pop_front() {
                     if let Some(p) = internal.next_send() {
@@ -533,17 +884,20 @@ macro_rules! shared_recv_impl {
                         // Safety: it's safe to receive from owned signal once
                         unsafe { internal.queue.push_back(p.recv()) }
                     }
-                    return Ok(Some(v));
+                    return Ok(v);
                 } else if let Some(p) = internal.next_send() {
                     // Safety: it's safe to receive from owned signal once
+                    let (data, handoff) = unsafe { p.recv_locked() };
                     drop(internal);
-                    return unsafe { Ok(Some(p.recv())) };
+                    handoff.finish();
+                    return Ok(data);
                 }
                 if internal.send_count == 0 {
-                    return Err(ReceiveError::SendClosed);
+                    return Err(TryRecvError::SendClosed);
                 }
+                return Err(TryRecvError::Empty);
             }
-            Ok(None)
+            Err(TryRecvError::Contended)
         }
 
         /// Returns, whether the send side of the channel, is closed or not
@@ -555,7 +909,7 @@ macro_rules! shared_recv_impl {
         /// assert_eq!(r.is_disconnected(),true);
         /// ```
         pub fn is_disconnected(&self) -> bool {
-            acquire_internal(&self.internal).send_count == 0
+            self.internal.snapshot.send_count() == 0
         }
 
         /// Returns, whether the channel receive side is terminated, and will not return any result in future recv calls.
@@ -573,13 +927,19 @@ macro_rules! shared_recv_impl {
         /// assert_eq!(r.is_terminated(),true);
         /// ```
         pub fn is_terminated(&self) -> bool {
-            let internal = acquire_internal(&self.internal);
-            internal.send_count == 0 && internal.queue.len() == 0
+            // the length is read after the counter, so it's not older than the closing of the send side
+            let snapshot = &self.internal.snapshot;
+            snapshot.send_count() == 0 && snapshot.len() == 0
         }
     };
 }
 
 impl<T> Sender<T> {
+    /// Returns a sender of the channel, the caller is responsible for counting the sender
+    #[inline(always)]
+    pub(crate) fn new(internal: Internal<T>) -> Self {
+        Self { internal }
+    }
     /// Sends data to the channel
     /// # Examples
     ///
@@ -595,24 +955,27 @@ impl<T> Sender<T> {
     /// # anyhow::Ok(())
     /// ```
     #[inline(always)]
-    pub fn send(&self, mut data: T) -> Result<(), SendError> {
+    pub fn send(&self, mut data: T) -> Result<(), SendError<T>> {
         let mut internal = acquire_internal(&self.internal);
-        if internal.recv_count == 0 {
+        if internal.recv_count == 0 || internal.send_count == 0 {
             let send_count = internal.send_count;
-            // Avoid wasting lock time on dropping failed send object
             drop(internal);
             if send_count == 0 {
-                return Err(SendError::Closed);
+                return Err(SendError::Closed(data));
             }
-            return Err(SendError::ReceiveClosed);
+            return Err(SendError::ReceiveClosed(data));
         }
         if let Some(first) = internal.next_recv() {
-            drop(internal);
             // Safety: it's safe to send to owned signal once
-            unsafe { first.send(data) }
+            let handoff = unsafe { first.send_locked(data) };
+            drop(internal);
+            handoff.finish();
             Ok(())
-        } else if internal.queue.len() < internal.capacity {
-            internal.queue.push_back(data);
+        } else if internal.can_buffer() {
+            let evicted = internal.buffer(data);
+            drop(internal);
+            // drop the evicted object outside of the lock
+            drop(evicted);
             Ok(())
         } else {
             // send directly to the waitlist
@@ -620,9 +983,11 @@ impl<T> Sender<T> {
             let sig = SyncSignal::new(KanalPtr::new_from(&mut data));
             let _sig_address_holder = &sig;
             internal.push_send(sig.as_signal());
+            let spins = internal.spins;
             drop(internal);
-            if !sig.wait() {
-                return Err(SendError::Closed);
+            if !sig.wait_spin(spins) {
+                // the signal is terminated, data is not moved and belongs to the caller again
+                return Err(SendError::Closed(data));
             }
             // data semantically is moved so forget about dropping it if it requires dropping
             if needs_drop::<T>() {
@@ -632,8 +997,48 @@ impl<T> Sender<T> {
         }
         // if the queue is not empty send the data
     }
-    /// Sends data to the channel with a deadline, if send fails then the object will be dropped.
-    /// you can use send_option_timeout if you like to keep the object in case of timeout.
+    /// Sends all objects of the iterator to the channel, objects are handed to waiting receivers and buffered
+    ///  in the queue under a single lock of the channel, and it only waits on the waitlist for the remainder.
+    /// It returns the count of sent objects, or an error with the count of accepted objects and the object that failed to be sent
+    ///  in case that channel is closed. Objects that are not taken yet stay in the iterator if it's passed by reference.
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::thread::spawn;
+    /// let (s, r) = kanal::bounded(4);
+    /// let t = spawn(move || r.take(10).sum::<u64>());
+    /// assert_eq!(s.send_many(1..=10)?, 10);
+    /// assert_eq!(t.join().unwrap(), 55);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn send_many<I: IntoIterator<Item = T>>(
+        &self,
+        items: I,
+    ) -> Result<usize, SendManyError<T>> {
+        let mut items = items.into_iter();
+        let mut count = 0;
+        loop {
+            match self.try_send_many(&mut items) {
+                Ok(accepted) => count += accepted,
+                Err(err) => {
+                    // the channel may be closed by the receiver right after the last object,
+                    //  so it's only an error if there is an object left, which is handed back inside the error
+                    return match items.next() {
+                        Some(data) => Err(err.after(count, data)),
+                        None => Ok(count),
+                    };
+                }
+            }
+            match items.next() {
+                Some(data) => {
+                    self.send(data).map_err(|err| err.into_many(count))?;
+                    count += 1;
+                }
+                None => return Ok(count),
+            }
+        }
+    }
+    /// Sends data to the channel with a deadline, if send fails then the object is handed back inside the error.
     /// # Examples
     ///
     /// ```
@@ -648,26 +1053,37 @@ impl<T> Sender<T> {
     /// # println!("Hello {}!",name);
     /// # anyhow::Ok(())
     /// ```
+    /// ```
+    /// # use std::time::Duration;
+    /// let (s, r) = kanal::bounded(0);
+    /// // there is no receiver waiting, so the object is returned back on timeout
+    /// let err = s.send_timeout("Hello",Duration::from_millis(10)).unwrap_err();
+    /// assert_eq!(err, kanal::SendErrorTimeout::Timeout("Hello"));
+    /// assert_eq!(err.into_inner(), "Hello");
+    /// ```
     #[inline(always)]
-    pub fn send_timeout(&self, mut data: T, duration: Duration) -> Result<(), SendErrorTimeout> {
+    pub fn send_timeout(&self, mut data: T, duration: Duration) -> Result<(), SendErrorTimeout<T>> {
         let deadline = Instant::now().checked_add(duration).unwrap();
         let mut internal = acquire_internal(&self.internal);
-        if internal.recv_count == 0 {
+        if internal.recv_count == 0 || internal.send_count == 0 {
             let send_count = internal.send_count;
-            // Avoid wasting lock time on dropping failed send object
             drop(internal);
             if send_count == 0 {
-                return Err(SendErrorTimeout::Closed);
+                return Err(SendErrorTimeout::Closed(data));
             }
-            return Err(SendErrorTimeout::ReceiveClosed);
+            return Err(SendErrorTimeout::ReceiveClosed(data));
         }
         if let Some(first) = internal.next_recv() {
-            drop(internal);
             // Safety: it's safe to send to owned signal once
-            unsafe { first.send(data) }
+            let handoff = unsafe { first.send_locked(data) };
+            drop(internal);
+            handoff.finish();
             Ok(())
-        } else if internal.queue.len() < internal.capacity {
-            internal.queue.push_back(data);
+        } else if internal.can_buffer() {
+            let evicted = internal.buffer(data);
+            drop(internal);
+            // drop the evicted object outside of the lock
+            drop(evicted);
             Ok(())
         } else {
             // send directly to the waitlist
@@ -678,32 +1094,34 @@ impl<T> Sender<T> {
             drop(internal);
             if !sig.wait_timeout(deadline) {
                 if sig.is_terminated() {
-                    return Err(SendErrorTimeout::Closed);
+                    return Err(SendErrorTimeout::Closed(data));
                 }
                 {
                     let mut internal = acquire_internal(&self.internal);
                     if internal.cancel_send_signal(sig.as_signal()) {
-                        return Err(SendErrorTimeout::Timeout);
+                        return Err(SendErrorTimeout::Timeout(data));
                     }
                 }
                 // removing receive failed to wait for the signal response
                 if !sig.wait() {
-                    return Err(SendErrorTimeout::Closed);
+                    return Err(SendErrorTimeout::Closed(data));
                 }
             }
             // data semantically is moved so forget about dropping it if it requires dropping
-            if std::mem::needs_drop::<T>() {
-                std::mem::forget(data);
+            if needs_drop::<T>() {
+                forget(data);
             }
             Ok(())
         }
         // if the queue is not empty send the data
     }
 
-    /// Tries to send data from provided option with a deadline, it will panic on successful send for None option.
+    /// Tries to send data from provided option with a deadline, the object is left in the option on failure.
+    /// It will panic if the option is `None`.
     /// # Examples
     ///
     /// ```
+    /// # #![allow(deprecated)]
     /// # use std::thread::spawn;
     /// # use std::time::Duration;
     /// # let (s, r) = kanal::bounded(0);
@@ -716,60 +1134,28 @@ impl<T> Sender<T> {
     /// # println!("Hello {}!",name);
     /// # anyhow::Ok(())
     /// ```
+    #[deprecated(note = "use `send_timeout`, its error hands the object back")]
     #[inline(always)]
     pub fn send_option_timeout(
         &self,
         data: &mut Option<T>,
         duration: Duration,
-    ) -> Result<(), SendErrorTimeout> {
-        let deadline = Instant::now().checked_add(duration).unwrap();
-        let mut internal = acquire_internal(&self.internal);
-        if internal.recv_count == 0 {
-            let send_count = internal.send_count;
-            // Avoid wasting lock time on dropping failed send object
-            drop(internal);
-            if send_count == 0 {
-                return Err(SendErrorTimeout::Closed);
-            }
-            return Err(SendErrorTimeout::ReceiveClosed);
-        }
-        if let Some(first) = internal.next_recv() {
-            drop(internal);
-            // Safety: it's safe to send to owned signal once
-            unsafe { first.send(data.take().unwrap()) }
-            Ok(())
-        } else if internal.queue.len() < internal.capacity {
-            internal.queue.push_back(data.take().unwrap());
-            Ok(())
-        } else {
-            // send directly to the waitlist
-            let mut d = data.take().unwrap();
-            let _data_address_holder = &d; // pin to address
-            let sig = SyncSignal::new(KanalPtr::new_from(&mut d));
-            let _sig_address_holder = &sig;
-            internal.push_send(sig.as_signal());
-            drop(internal);
-            if !sig.wait_timeout(deadline) {
-                if sig.is_terminated() {
-                    *data = Some(d);
-                    return Err(SendErrorTimeout::Closed);
+    ) -> Result<(), SendErrorTimeout<()>> {
+        self.send_timeout(data.take().unwrap(), duration)
+            .map_err(|err| match err {
+                SendErrorTimeout::Closed(v) => {
+                    *data = Some(v);
+                    SendErrorTimeout::Closed(())
                 }
-                {
-                    let mut internal = acquire_internal(&self.internal);
-                    if internal.cancel_send_signal(sig.as_signal()) {
-                        *data = Some(d);
-                        return Err(SendErrorTimeout::Timeout);
-                    }
+                SendErrorTimeout::ReceiveClosed(v) => {
+                    *data = Some(v);
+                    SendErrorTimeout::ReceiveClosed(())
                 }
-                // removing receive failed to wait for the signal response
-                if !sig.wait() {
-                    *data = Some(d);
-                    return Err(SendErrorTimeout::Closed);
+                SendErrorTimeout::Timeout(v) => {
+                    *data = Some(v);
+                    SendErrorTimeout::Timeout(())
                 }
-            }
-            Ok(())
-        }
-        // if the queue is not empty send the data
+            })
     }
     shared_send_impl!();
     /// Clones Sender as the async version of it and returns it
@@ -779,15 +1165,21 @@ impl<T> Sender<T> {
         if internal.send_count > 0 {
             internal.send_count += 1;
         }
-        AsyncSender::<T> {
-            internal: self.internal.clone(),
-        }
+        AsyncSender::new(self.internal.clone())
     }
     shared_impl!();
 }
 
 #[cfg(feature = "async")]
 impl<T> AsyncSender<T> {
+    /// Returns a sender of the channel, the caller is responsible for counting the sender
+    #[inline(always)]
+    pub(crate) fn new(internal: Internal<T>) -> Self {
+        Self {
+            internal,
+            slot: PollSlot::default(),
+        }
+    }
     /// Sends data asynchronously to the channel
     /// # Examples
     ///
@@ -801,44 +1193,230 @@ impl<T> AsyncSender<T> {
     /// ```
     #[inline(always)]
     pub fn send(&'_ self, data: T) -> SendFuture<'_, T> {
-        if size_of::<T>() > size_of::<*mut T>() {
-            SendFuture {
-                state: FutureState::Zero,
-                internal: &self.internal,
-                sig: AsyncSignal::new(),
-                data: MaybeUninit::new(data),
+        SendFuture::new_ref(&self.internal, data)
+    }
+    /// Sends data asynchronously to the channel with a future that owns a reference to the channel,
+    ///  so unlike `send` the returned future does not borrow the sender and can be spawned or stored freely.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let (s, r) = kanal::bounded_async(0);
+    /// let future = s.send_owned(1);
+    /// drop(s);
+    /// tokio::spawn(future);
+    /// assert_eq!(r.recv().await?, 1);
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn send_owned(&self, data: T) -> OwnedSendFuture<T> {
+        OwnedSendFuture::new_owned(self.internal.clone(), data)
+    }
+    /// Sends all objects of the iterator asynchronously to the channel, objects are handed to waiting receivers and buffered
+    ///  in the queue under a single lock of the channel, and it only waits on the waitlist for the remainder.
+    /// It returns the count of sent objects, or an error with the count of accepted objects and the object that failed to be sent
+    ///  in case that channel is closed. Objects that are not taken yet stay in the iterator if it's passed by reference.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let (s, r) = kanal::bounded_async(4);
+    /// let t = tokio::spawn(async move {
+    ///     let mut sum = 0;
+    ///     for _ in 0..10 {
+    ///         sum += r.recv().await.unwrap();
+    ///     }
+    ///     sum
+    /// });
+    /// assert_eq!(s.send_many(1..=10).await?, 10);
+    /// assert_eq!(t.await?, 55);
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    pub async fn send_many<I: IntoIterator<Item = T>>(
+        &self,
+        items: I,
+    ) -> Result<usize, SendManyError<T>> {
+        let mut items = items.into_iter();
+        let mut count = 0;
+        loop {
+            match self.try_send_many(&mut items) {
+                Ok(accepted) => count += accepted,
+                Err(err) => {
+                    // the channel may be closed by the receiver right after the last object,
+                    //  so it's only an error if there is an object left, which is handed back inside the error
+                    return match items.next() {
+                        Some(data) => Err(err.after(count, data)),
+                        None => Ok(count),
+                    };
+                }
             }
-        } else {
-            SendFuture {
-                state: FutureState::Zero,
-                internal: &self.internal,
-                sig: AsyncSignal::new_inside_ptr(KanalPtr::new_owned(data)),
-                data: MaybeUninit::uninit(),
+            match items.next() {
+                Some(data) => {
+                    self.send(data).await.map_err(|err| err.into_many(count))?;
+                    count += 1;
+                }
+                None => return Ok(count),
             }
         }
     }
-    shared_send_impl!();
-    /// Clones async sender as sync version of it
+    /// Sends data asynchronously to the channel with a timeout, if send fails then the object is handed back inside the error.
+    /// The timeout is tracked by kanal itself, so it works with any async runtime.
     /// # Examples
     ///
     /// ```
     /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
-    /// let (s, r) = kanal::unbounded_async();
-    /// let sync_sender=s.clone_sync();
-    /// // JUST FOR EXAMPLE IT IS WRONG TO USE SYNC INSTANCE IN ASYNC CONTEXT
-    /// sync_sender.send(1)?;
-    /// assert_eq!(r.recv().await?,1);
+    /// # use std::time::Duration;
+    /// let (s, r) = kanal::bounded_async(0);
+    /// // there is no receiver waiting on a zero sized channel so it times out
+    /// assert_eq!(
+    ///     s.send_timeout(1, Duration::from_millis(10)).await,
+    ///     Err(kanal::SendErrorTimeout::Timeout(1))
+    /// );
     /// # anyhow::Ok(())
     /// # });
     /// ```
-    pub fn clone_sync(&self) -> Sender<T> {
-        let mut internal = acquire_internal(&self.internal);
-        if internal.send_count > 0 {
-            internal.send_count += 1;
+    #[inline(always)]
+    pub fn send_timeout(&'_ self, data: T, duration: Duration) -> SendTimeoutFuture<'_, T> {
+        self.send_deadline(data, Instant::now().checked_add(duration).unwrap())
+    }
+    /// Sends data asynchronously to the channel before the deadline, if send fails then the object is handed back inside the error.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// # use std::time::{Duration, Instant};
+    /// let (s, r) = kanal::bounded_async(1);
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// s.send_deadline(1, deadline).await?;
+    /// assert_eq!(
+    ///     s.send_deadline(2, deadline).await,
+    ///     Err(kanal::SendErrorTimeout::Timeout(2))
+    /// );
+    /// assert_eq!(r.recv().await?, 1);
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn send_deadline(&'_ self, data: T, deadline: Instant) -> SendTimeoutFuture<'_, T> {
+        SendTimeoutFuture {
+            future: self.send(data),
+            delay: Delay::new(deadline),
         }
-        Sender::<T> {
-            internal: self.internal.clone(),
+    }
+    /// Converts the sender into a sink that implements `futures::Sink`, so it can be used with sink combinators like `StreamExt::forward`.
+    /// Closing the sink drops the sender, so the send side of the channel gets closed when it is the last sender.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// use futures::{SinkExt, StreamExt};
+    ///
+    /// let (s, r) = kanal::bounded_async(1);
+    /// let mut sink = s.into_sink();
+    /// tokio::spawn(async move {
+    ///     sink.send_all(&mut futures::stream::iter(1..=3).map(Ok)).await?;
+    ///     sink.close().await
+    /// });
+    /// assert_eq!(r.stream().collect::<Vec<_>>().await, vec![1, 2, 3]);
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    pub fn into_sink(self) -> SendSink<T> {
+        SendSink::new(self)
+    }
+    /// Polls the sender for room to start sending an object with [`start_send`](AsyncSender::start_send),
+    ///  it's the building block of hand-written futures and sinks that can't store a [`SendFuture`] next to the sender.
+    /// It's ready once the object of the previous `start_send` is delivered to the channel and the channel has room for
+    ///  the next object or a receiver is waiting. The object in flight is handed back inside the error if the channel gets
+    ///  closed while it's waiting, errors of a closed channel without an object in flight hold no object.
+    /// Readiness is not a reservation, if another sender takes the room first, the next object waits in the channel waitlist.
+    /// The operation lives in a waiter slot of this sender handle, every task that polls a shared handle is woken when
+    ///  the slot makes progress.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// use std::future::poll_fn;
+    ///
+    /// let (s, r) = kanal::bounded_async(0);
+    /// tokio::spawn(async move {
+    ///     for i in 1..=3 {
+    ///         poll_fn(|cx| s.poll_send_ready(cx)).await?;
+    ///         s.start_send(i)?;
+    ///     }
+    ///     poll_fn(|cx| s.poll_flush(cx)).await?;
+    ///     anyhow::Ok(())
+    /// });
+    /// assert_eq!(r.recv().await?, 1);
+    /// assert_eq!(r.recv().await?, 2);
+    /// assert_eq!(r.recv().await?, 3);
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    pub fn poll_send_ready(
+        &self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), SendError<Option<T>>>> {
+        self.slot
+            .with(|slot| slot.poll_send_ready(&self.internal, cx))
+    }
+    /// Starts sending the object, see [`poll_send_ready`](AsyncSender::poll_send_ready).
+    /// The object is sent right away if a receiver is waiting or the channel has room for it, otherwise it waits
+    ///  in the channel waitlist and `poll_send_ready` returns ready once it's delivered.
+    /// [`poll_flush`](AsyncSender::poll_flush) must be polled to ready after the last `start_send` to flush the object
+    ///  and observe its result.
+    /// The object is handed back with [`TrySendError::Full`] if the object of the previous `start_send` is still waiting,
+    ///  i.e. it's called without a successful `poll_send_ready`.
+    /// An object that is still waiting when the sender is dropped is dropped with it.
+    pub fn start_send(&self, data: T) -> Result<(), TrySendError<T>> {
+        self.slot.with(|slot| slot.start_send(&self.internal, data))
+    }
+    /// Drives the object of the last [`start_send`](AsyncSender::start_send) to the channel, it's ready once the object
+    ///  is delivered or when there is no object in flight, and the object is handed back inside the error if the channel
+    ///  gets closed while it's waiting.
+    /// Unlike [`poll_send_ready`](AsyncSender::poll_send_ready), it doesn't wait for room to send another object.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// use std::future::poll_fn;
+    ///
+    /// let (s, r) = kanal::bounded_async(0);
+    /// s.start_send(1)?;
+    /// assert!(futures::poll!(poll_fn(|cx| s.poll_flush(cx))).is_pending());
+    /// assert_eq!(r.recv().await?, 1);
+    /// poll_fn(|cx| s.poll_flush(cx)).await?;
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    pub fn poll_flush(
+        &self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), SendError<T>>> {
+        self.slot.with(|slot| slot.poll_flush(&self.internal, cx))
+    }
+    shared_send_impl!();
+    /// Clones async sender as sync version of it
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let (s, r) = kanal::unbounded_async();
+    /// let sync_sender=s.clone_sync();
+    /// // JUST FOR EXAMPLE IT IS WRONG TO USE SYNC INSTANCE IN ASYNC CONTEXT
+    /// sync_sender.send(1)?;
+    /// assert_eq!(r.recv().await?,1);
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    pub fn clone_sync(&self) -> Sender<T> {
+        let mut internal = acquire_internal(&self.internal);
+        if internal.send_count > 0 {
+            internal.send_count += 1;
         }
+        Sender::new(self.internal.clone())
     }
 
     shared_impl!();
@@ -857,7 +1435,7 @@ pub struct Receiver<T> {
 
 impl<T> Debug for Receiver<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Receiver {{ .. }}")
+        debug_channel(f, "Receiver", &self.internal)
     }
 }
 
@@ -870,16 +1448,23 @@ impl<T> Debug for Receiver<T> {
 #[cfg(feature = "async")]
 pub struct AsyncReceiver<T> {
     internal: Internal<T>,
+    /// Waiter slot of `poll_recv`
+    slot: PollSlot<T>,
 }
 
 #[cfg(feature = "async")]
 impl<T> Debug for AsyncReceiver<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "AsyncReceiver {{ .. }}")
+        debug_channel(f, "AsyncReceiver", &self.internal)
     }
 }
 
 impl<T> Receiver<T> {
+    /// Returns a receiver of the channel, the caller is responsible for counting the receiver
+    #[inline(always)]
+    pub(crate) fn new(internal: Internal<T>) -> Self {
+        Self { internal }
+    }
     /// Receives data from the channel
     #[inline(always)]
     pub fn recv(&self) -> Result<T, ReceiveError> {
@@ -895,9 +1480,11 @@ impl<T> Receiver<T> {
             }
             Ok(v)
         } else if let Some(p) = internal.next_send() {
-            drop(internal);
             // Safety: it's safe to receive from owned signal once
-            unsafe { Ok(p.recv()) }
+            let (data, handoff) = unsafe { p.recv_locked() };
+            drop(internal);
+            handoff.finish();
+            Ok(data)
         } else {
             if internal.send_count == 0 {
                 return Err(ReceiveError::SendClosed);
@@ -909,10 +1496,11 @@ impl<T> Receiver<T> {
             let sig = SyncSignal::new(KanalPtr::new_write_address_ptr(ret.as_mut_ptr()));
             let _sig_address_holder = &sig;
             internal.push_recv(sig.as_signal());
+            let spins = internal.spins;
             drop(internal);
 
-            if !sig.wait() {
-                return Err(ReceiveError::Closed);
+            if !sig.wait_spin(spins) {
+                return Err(terminated_recv_error(&self.internal));
             }
 
             // Safety: it's safe to assume init as data is forgotten on another side
@@ -924,10 +1512,97 @@ impl<T> Receiver<T> {
         }
         // if the queue is not empty send the data
     }
+    /// Receives up to `max` objects from the channel to the end of the vector, it waits until at least one object is available
+    ///  and then moves the available objects from the queue and from waiting senders under a single lock of the channel.
+    /// It returns the count of received objects, which is zero only if `max` is zero.
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = kanal::bounded(8);
+    /// s.send_many(1..=5)?;
+    /// let mut buf = Vec::new();
+    /// assert_eq!(r.recv_many(&mut buf, 10)?, 5);
+    /// assert_eq!(buf, vec![1, 2, 3, 4, 5]);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn recv_many(&self, buf: &mut Vec<T>, max: usize) -> Result<usize, ReceiveError> {
+        loop {
+            match self.try_recv_many(buf, max) {
+                Ok(count) => return Ok(count),
+                Err(TryRecvError::Closed) => return Err(ReceiveError::Closed),
+                Err(TryRecvError::SendClosed) => return Err(ReceiveError::SendClosed),
+                Err(TryRecvError::Empty | TryRecvError::Contended) => self.wait_ready(None),
+            }
+        }
+    }
+    /// Receives a batch of up to `max` objects from the channel, it waits until the first object is available
+    ///  and then collects objects that arrive within the `window` after it, and returns the batch once it's full
+    ///  or the window is passed. The window is measured once from the arrival of the first object.
+    /// It returns an error only if the channel is closed before the first object, a batch that is cut short
+    ///  by the closing of the channel is returned as is. The batch is empty only if `max` is zero.
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// let (s, r) = kanal::unbounded();
+    /// s.send_many(1..=3)?;
+    /// let batch = r.recv_batch_timeout(10, Duration::from_millis(10))?;
+    /// assert_eq!(batch, vec![1, 2, 3]);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn recv_batch_timeout(&self, max: usize, window: Duration) -> Result<Vec<T>, ReceiveError> {
+        let mut batch = Vec::new();
+        if max == 0 {
+            return Ok(batch);
+        }
+        self.recv_many(&mut batch, max)?;
+        let deadline = Instant::now().checked_add(window).unwrap();
+        while batch.len() < max {
+            let remaining = max - batch.len();
+            match self.try_recv_many(&mut batch, remaining) {
+                Ok(_) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Contended) => {}
+                Err(_) => break,
+            }
+            if Instant::now() >= deadline {
+                break;
+            }
+            self.wait_ready(Some(deadline));
+        }
+        Ok(batch)
+    }
+    /// Waits in the receive waitlist without taking an object until a receive operation can complete without waiting,
+    ///  or the deadline is reached, so batch receives drain the channel under a single lock after each wake up
+    fn wait_ready(&self, deadline: Option<Instant>) {
+        let entry = SelectEntry::<T>::new();
+        let sig = SyncSignal::<()>::new(KanalPtr::default());
+        {
+            let mut internal = acquire_internal(&self.internal);
+            if internal.recv_ready() {
+                return;
+            }
+            // Safety: the entry is not linked, and it's unlinked below before it's released
+            internal.push_recv(unsafe { entry.bind(SelectSignal::Sync(&sig)) });
+        }
+        match deadline {
+            Some(deadline) => {
+                sig.wait_timeout(deadline);
+            }
+            None => {
+                sig.wait();
+            }
+        }
+        // the entry is already unlinked if a sender notified it
+        acquire_internal(&self.internal).cancel_recv_signal(entry.as_signal());
+    }
     /// Tries receiving from the channel within a duration
     #[inline(always)]
     pub fn recv_timeout(&self, duration: Duration) -> Result<T, ReceiveErrorTimeout> {
-        let deadline = Instant::now().checked_add(duration).unwrap();
+        self.recv_deadline(Instant::now().checked_add(duration).unwrap())
+    }
+    /// Tries receiving from the channel before the deadline
+    #[inline(always)]
+    fn recv_deadline(&self, deadline: Instant) -> Result<T, ReceiveErrorTimeout> {
         let mut internal = acquire_internal(&self.internal);
         if internal.recv_count == 0 {
             return Err(ReceiveErrorTimeout::Closed);
@@ -940,9 +1615,11 @@ impl<T> Receiver<T> {
             }
             Ok(v)
         } else if let Some(p) = internal.next_send() {
-            drop(internal);
             // Safety: it's safe to receive from owned signal once
-            unsafe { Ok(p.recv()) }
+            let (data, handoff) = unsafe { p.recv_locked() };
+            drop(internal);
+            handoff.finish();
+            Ok(data)
         } else {
             if Instant::now() > deadline {
                 return Err(ReceiveErrorTimeout::Timeout);
@@ -960,7 +1637,7 @@ impl<T> Receiver<T> {
             drop(internal);
             if !sig.wait_timeout(deadline) {
                 if sig.is_terminated() {
-                    return Err(ReceiveErrorTimeout::Closed);
+                    return Err(terminated_recv_error(&self.internal).into());
                 }
                 {
                     let mut internal = acquire_internal(&self.internal);
@@ -970,7 +1647,7 @@ impl<T> Receiver<T> {
                 }
                 // removing receive failed to wait for the signal response
                 if !sig.wait() {
-                    return Err(ReceiveErrorTimeout::Closed);
+                    return Err(terminated_recv_error(&self.internal).into());
                 }
             }
             // Safety: it's safe to assume init as data is forgotten on another side
@@ -990,9 +1667,7 @@ impl<T> Receiver<T> {
         if internal.recv_count > 0 {
             internal.recv_count += 1;
         }
-        AsyncReceiver::<T> {
-            internal: self.internal.clone(),
-        }
+        AsyncReceiver::new(self.internal.clone())
     }
     shared_impl!();
 }
@@ -1001,15 +1676,20 @@ impl<T> Iterator for Receiver<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.recv() {
-            Ok(d) => Some(d),
-            Err(_) => None,
-        }
+        self.recv().ok()
     }
 }
 
 #[cfg(feature = "async")]
 impl<T> AsyncReceiver<T> {
+    /// Returns a receiver of the channel, the caller is responsible for counting the receiver
+    #[inline(always)]
+    pub(crate) fn new(internal: Internal<T>) -> Self {
+        Self {
+            internal,
+            slot: PollSlot::default(),
+        }
+    }
     /// Returns a future to receive data from the channel asynchronously
     /// # Examples
     ///
@@ -1030,6 +1710,135 @@ impl<T> AsyncReceiver<T> {
     pub fn recv(&'_ self) -> ReceiveFuture<'_, T> {
         ReceiveFuture::new_ref(&self.internal)
     }
+    /// Returns a future to receive data from the channel asynchronously that owns a reference to the channel,
+    ///  so unlike `recv` the returned future does not borrow the receiver and can be spawned or stored freely.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let (s, r) = kanal::bounded_async(0);
+    /// let handle = tokio::spawn(r.recv_owned());
+    /// s.send(1).await?;
+    /// assert_eq!(handle.await??, 1);
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn recv_owned(&self) -> OwnedReceiveFuture<T> {
+        OwnedReceiveFuture::new_owned(self.internal.clone())
+    }
+    /// Receives up to `max` objects asynchronously from the channel to the end of the vector, it waits until at least one object
+    ///  is available and then moves the available objects from the queue and from waiting senders under a single lock of the channel.
+    /// It returns the count of received objects, which is zero only if `max` is zero.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let (s, r) = kanal::bounded_async(8);
+    /// s.send_many(1..=5).await?;
+    /// let mut buf = Vec::new();
+    /// assert_eq!(r.recv_many(&mut buf, 10).await?, 5);
+    /// assert_eq!(buf, vec![1, 2, 3, 4, 5]);
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    pub async fn recv_many(&self, buf: &mut Vec<T>, max: usize) -> Result<usize, ReceiveError> {
+        loop {
+            match self.try_recv_many(buf, max) {
+                Ok(count) => return Ok(count),
+                Err(TryRecvError::Closed) => return Err(ReceiveError::Closed),
+                Err(TryRecvError::SendClosed) => return Err(ReceiveError::SendClosed),
+                Err(TryRecvError::Empty | TryRecvError::Contended) => {
+                    RecvReadyFuture::new(&self.internal, None).await
+                }
+            }
+        }
+    }
+    /// Receives a batch of up to `max` objects asynchronously from the channel, it waits until the first object is available
+    ///  and then collects objects that arrive within the `window` after it, and returns the batch once it's full
+    ///  or the window is passed. The window is measured once from the arrival of the first object.
+    /// It returns an error only if the channel is closed before the first object, a batch that is cut short
+    ///  by the closing of the channel is returned as is. The batch is empty only if `max` is zero.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// # use std::time::Duration;
+    /// let (s, r) = kanal::unbounded_async();
+    /// s.send_many(1..=3).await?;
+    /// let batch = r.recv_batch_timeout(10, Duration::from_millis(10)).await?;
+    /// assert_eq!(batch, vec![1, 2, 3]);
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    pub async fn recv_batch_timeout(
+        &self,
+        max: usize,
+        window: Duration,
+    ) -> Result<Vec<T>, ReceiveError> {
+        let mut batch = Vec::new();
+        if max == 0 {
+            return Ok(batch);
+        }
+        self.recv_many(&mut batch, max).await?;
+        let deadline = Instant::now().checked_add(window).unwrap();
+        while batch.len() < max {
+            let remaining = max - batch.len();
+            match self.try_recv_many(&mut batch, remaining) {
+                Ok(_) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Contended) => {}
+                Err(_) => break,
+            }
+            if Instant::now() >= deadline {
+                break;
+            }
+            RecvReadyFuture::new(&self.internal, Some(deadline)).await;
+        }
+        Ok(batch)
+    }
+    /// Returns a future to receive data from the channel asynchronously with a timeout.
+    /// The timeout is tracked by kanal itself, so it works with any async runtime.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// # use std::time::Duration;
+    /// let (s, r) = kanal::bounded_async::<u64>(0);
+    /// assert_eq!(
+    ///     r.recv_timeout(Duration::from_millis(10)).await,
+    ///     Err(kanal::ReceiveErrorTimeout::Timeout)
+    /// );
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn recv_timeout(&'_ self, duration: Duration) -> ReceiveTimeoutFuture<'_, T> {
+        self.recv_deadline(Instant::now().checked_add(duration).unwrap())
+    }
+    /// Returns a future to receive data from the channel asynchronously before the deadline.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// # use std::time::{Duration, Instant};
+    /// let (s, r) = kanal::unbounded_async();
+    /// s.send(1).await?;
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// assert_eq!(r.recv_deadline(deadline).await?, 1);
+    /// assert_eq!(
+    ///     r.recv_deadline(deadline).await,
+    ///     Err(kanal::ReceiveErrorTimeout::Timeout)
+    /// );
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn recv_deadline(&'_ self, deadline: Instant) -> ReceiveTimeoutFuture<'_, T> {
+        ReceiveTimeoutFuture {
+            future: self.recv(),
+            delay: Delay::new(deadline),
+        }
+    }
     /// Creates a asynchronous stream for the channel to receive messages,
     ///  `ReceiveStream` borrows the receiver, after dropping it, receiver will be available and usable again.
     ///
@@ -1062,6 +1871,71 @@ impl<T> AsyncReceiver<T> {
     pub fn stream(&'_ self) -> ReceiveStream<'_, T> {
         ReceiveStream::new_borrowed(self)
     }
+    /// Converts the receiver into an owned asynchronous stream, unlike `stream` it does not borrow the receiver,
+    ///  so it can be spawned or stored freely. The receiver can be taken back with `OwnedReceiveStream::into_inner`.
+    ///
+    /// # Examples
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// use futures::stream::StreamExt;
+    ///
+    /// let (s, r) = kanal::unbounded_async();
+    /// let handle = tokio::spawn(r.into_stream().collect::<Vec<_>>());
+    /// for i in 0..10 {
+    ///     s.send(i).await?;
+    /// }
+    /// drop(s);
+    /// assert_eq!(handle.await?, (0..10).collect::<Vec<_>>());
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn into_stream(self) -> OwnedReceiveStream<T> {
+        OwnedReceiveStream::new_owned(self)
+    }
+    /// Polls the receiver to receive an object from the channel, it's the building block of hand-written futures and streams
+    ///  that can't store a [`ReceiveFuture`] next to the receiver.
+    /// A pending poll only leaves a notification in the channel waitlist, the object is taken from the channel when
+    ///  `poll_recv` returns it, so objects are not held back from other receivers if the receiver is not polled again.
+    /// The waiter lives in a slot of this receiver handle, every task that polls a shared handle is woken when
+    ///  the channel gets ready.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// use std::future::poll_fn;
+    ///
+    /// let (s, r) = kanal::bounded_async(0);
+    /// tokio::spawn(async move { s.send(1).await });
+    /// assert_eq!(poll_fn(|cx| r.poll_recv(cx)).await?, 1);
+    /// assert_eq!(
+    ///     poll_fn(|cx| r.poll_recv(cx)).await,
+    ///     Err(kanal::ReceiveError::SendClosed)
+    /// );
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    pub fn poll_recv(
+        &self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<T, ReceiveError>> {
+        self.slot.with(|slot| {
+            let res = loop {
+                match self.try_recv() {
+                    Ok(data) => break Ok(data),
+                    Err(TryRecvError::Closed) => break Err(ReceiveError::Closed),
+                    Err(TryRecvError::SendClosed) => break Err(ReceiveError::SendClosed),
+                    Err(TryRecvError::Empty | TryRecvError::Contended) => {}
+                }
+                // the channel is checked again if it got ready before the waiter is registered
+                if slot.poll_recv_ready(&self.internal, cx).is_pending() {
+                    return std::task::Poll::Pending;
+                }
+            };
+            slot.finish_recv(&self.internal, cx);
+            std::task::Poll::Ready(res)
+        })
+    }
     shared_recv_impl!();
     /// Returns sync cloned version of the receiver
     /// # Examples
@@ -1082,9 +1956,7 @@ impl<T> AsyncReceiver<T> {
         if internal.recv_count > 0 {
             internal.recv_count += 1;
         }
-        Receiver::<T> {
-            internal: self.internal.clone(),
-        }
+        Receiver::new(self.internal.clone())
     }
     shared_impl!();
 }
@@ -1104,6 +1976,7 @@ impl<T> Drop for Receiver<T> {
 #[cfg(feature = "async")]
 impl<T> Drop for AsyncReceiver<T> {
     fn drop(&mut self) {
+        self.slot.cancel_recv(&self.internal);
         let mut internal = acquire_internal(&self.internal);
         if internal.recv_count > 0 {
             internal.recv_count -= 1;
@@ -1120,9 +1993,7 @@ impl<T> Clone for Receiver<T> {
         if internal.recv_count > 0 {
             internal.recv_count += 1;
         }
-        Self {
-            internal: self.internal.clone(),
-        }
+        Self::new(self.internal.clone())
     }
 }
 
@@ -1133,9 +2004,7 @@ impl<T> Clone for AsyncReceiver<T> {
         if internal.recv_count > 0 {
             internal.recv_count += 1;
         }
-        Self {
-            internal: self.internal.clone(),
-        }
+        Self::new(self.internal.clone())
     }
 }
 
@@ -1165,13 +2034,8 @@ impl<T> Clone for AsyncReceiver<T> {
 /// assert_eq!(total, 39600);
 /// ```
 pub fn bounded<T>(size: usize) -> (Sender<T>, Receiver<T>) {
-    let internal = ChannelInternal::new(true, size);
-    (
-        Sender {
-            internal: internal.clone(),
-        },
-        Receiver { internal },
-    )
+    let internal = ChannelInternal::new(true, size).into_shared();
+    (Sender::new(internal.clone()), Receiver::new(internal))
 }
 
 /// Returns bounded, async sender and receiver of the channel for type T
@@ -1195,15 +2059,65 @@ pub fn bounded<T>(size: usize) -> (Sender<T>, Receiver<T>) {
 /// ```
 #[cfg(feature = "async")]
 pub fn bounded_async<T>(size: usize) -> (AsyncSender<T>, AsyncReceiver<T>) {
-    let internal = ChannelInternal::new(true, size);
+    let internal = ChannelInternal::new(true, size).into_shared();
     (
-        AsyncSender {
-            internal: internal.clone(),
-        },
-        AsyncReceiver { internal },
+        AsyncSender::new(internal.clone()),
+        AsyncReceiver::new(internal),
     )
 }
 
+/// Returns bounded, sync sender and receiver of the channel for type T that handles a full queue based on the overflow policy.
+/// With `DropOldest` and `DropNewest` policies senders never wait, and evicted objects are dropped and counted,
+///  or handed back by `try_send_evicting`.
+/// senders and receivers can produce both async and sync versions via clone, clone_sync, and clone_async
+/// # Panics
+/// Panics if the policy is not `Block` and the size is zero, as every object would be evicted.
+/// # Examples
+///
+/// ```
+/// use kanal::OverflowPolicy;
+///
+/// let (s, r) = kanal::bounded_with_overflow(2, OverflowPolicy::DropOldest);
+/// s.send("frame 1")?;
+/// s.send("frame 2")?;
+/// assert_eq!(s.try_send_evicting("frame 3")?, Some("frame 1"));
+/// assert_eq!(r.recv()?, "frame 2");
+/// assert_eq!(r.recv()?, "frame 3");
+/// # anyhow::Ok(())
+/// ```
+pub fn bounded_with_overflow<T>(size: usize, overflow: OverflowPolicy) -> (Sender<T>, Receiver<T>) {
+    Builder::new().bounded(size).overflow(overflow).build()
+}
+
+/// Returns bounded, async sender and receiver of the channel for type T that handles a full queue based on the overflow policy.
+/// With `DropOldest` and `DropNewest` policies senders never wait, and evicted objects are dropped and counted,
+///  or handed back by `try_send_evicting`.
+/// senders and receivers can produce both async and sync versions via clone, clone_sync, and clone_async
+/// # Panics
+/// Panics if the policy is not `Block` and the size is zero, as every object would be evicted.
+/// # Examples
+///
+/// ```
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let (s, r) = kanal::bounded_async_with_overflow(1, kanal::OverflowPolicy::DropOldest);
+/// s.send(1).await?;
+/// s.send(2).await?;
+/// assert_eq!(r.evicted_count(), 1);
+/// assert_eq!(r.recv().await?, 2);
+/// anyhow::Ok(())
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub fn bounded_async_with_overflow<T>(
+    size: usize,
+    overflow: OverflowPolicy,
+) -> (AsyncSender<T>, AsyncReceiver<T>) {
+    Builder::new()
+        .bounded(size)
+        .overflow(overflow)
+        .build_async()
+}
+
 const UNBOUNDED_STARTING_SIZE: usize = 2048;
 
 /// Returns unbounded, sync sender and receiver of the channel for type T
@@ -1232,13 +2146,8 @@ const UNBOUNDED_STARTING_SIZE: usize = 2048;
 /// assert_eq!(total, 39600);
 /// ```
 pub fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
-    let internal = ChannelInternal::new(false, UNBOUNDED_STARTING_SIZE);
-    (
-        Sender {
-            internal: internal.clone(),
-        },
-        Receiver { internal },
-    )
+    let internal = ChannelInternal::new(false, UNBOUNDED_STARTING_SIZE).into_shared();
+    (Sender::new(internal.clone()), Receiver::new(internal))
 }
 
 /// Returns unbounded, async sender and receiver of the channel for type T
@@ -1262,11 +2171,223 @@ pub fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
 /// ```
 #[cfg(feature = "async")]
 pub fn unbounded_async<T>() -> (AsyncSender<T>, AsyncReceiver<T>) {
-    let internal = ChannelInternal::new(false, UNBOUNDED_STARTING_SIZE);
+    let internal = ChannelInternal::new(false, UNBOUNDED_STARTING_SIZE).into_shared();
     (
-        AsyncSender {
-            internal: internal.clone(),
-        },
-        AsyncReceiver { internal },
+        AsyncSender::new(internal.clone()),
+        AsyncReceiver::new(internal),
     )
 }
+
+/// Returns bounded, sync sender and receiver of a priority channel for type T, the receiver always gets the greatest buffered object first
+///  based on `Ord` of T, and objects with equal priority are received in the order they are sent.
+/// When there is no buffered object, sent objects are handed directly to waiting receivers like regular channels.
+/// Use [`PriorityOrder`] for a custom ordering or aging of low-priority objects.
+/// # Examples
+///
+/// ```
+/// let (s, r) = kanal::priority_bounded(8);
+/// for v in [2, 7, 1, 7, 5] {
+///     s.send(v)?;
+/// }
+/// let received: Vec<u32> = (0..5).map(|_| r.recv().unwrap()).collect();
+/// assert_eq!(received, [7, 7, 5, 2, 1]);
+/// # anyhow::Ok(())
+/// ```
+pub fn priority_bounded<T: Ord>(size: usize) -> (Sender<T>, Receiver<T>) {
+    PriorityOrder::natural().bounded(size)
+}
+
+/// Returns bounded, async sender and receiver of a priority channel for type T, the receiver always gets the greatest buffered object first
+///  based on `Ord` of T, and objects with equal priority are received in the order they are sent.
+/// # Examples
+///
+/// ```
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let (s, r) = kanal::priority_bounded_async(8);
+/// s.send(1).await?;
+/// s.send(3).await?;
+/// assert_eq!(r.recv().await?, 3);
+/// assert_eq!(r.recv().await?, 1);
+/// anyhow::Ok(())
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub fn priority_bounded_async<T: Ord>(size: usize) -> (AsyncSender<T>, AsyncReceiver<T>) {
+    PriorityOrder::natural().bounded_async(size)
+}
+
+/// Returns unbounded, sync sender and receiver of a priority channel for type T, the receiver always gets the greatest buffered object first
+///  based on `Ord` of T, and objects with equal priority are received in the order they are sent.
+/// # Examples
+///
+/// ```
+/// let (s, r) = kanal::priority_unbounded();
+/// s.send((1, "low"))?;
+/// s.send((9, "urgent"))?;
+/// assert_eq!(r.recv()?, (9, "urgent"));
+/// # anyhow::Ok(())
+/// ```
+pub fn priority_unbounded<T: Ord>() -> (Sender<T>, Receiver<T>) {
+    PriorityOrder::natural().unbounded()
+}
+
+/// Returns unbounded, async sender and receiver of a priority channel for type T, the receiver always gets the greatest buffered object first
+///  based on `Ord` of T, and objects with equal priority are received in the order they are sent.
+/// # Examples
+///
+/// ```
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let (s, r) = kanal::priority_unbounded_async();
+/// s.send((1, "low")).await?;
+/// s.send((9, "urgent")).await?;
+/// assert_eq!(r.recv().await?, (9, "urgent"));
+/// anyhow::Ok(())
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub fn priority_unbounded_async<T: Ord>() -> (AsyncSender<T>, AsyncReceiver<T>) {
+    PriorityOrder::natural().unbounded_async()
+}
+
+/// Returns sync sender and receiver of a oneshot channel for type T, it carries exactly one object
+///  with a lock-free handoff and without the queue and waitlists of the regular channels.
+/// The receiver can be converted to the async version of it with `to_async`.
+/// # Examples
+///
+/// ```
+/// use std::thread::spawn;
+///
+/// let (s, r) = kanal::oneshot();
+/// spawn(move || {
+///     s.send("response").unwrap();
+/// });
+/// assert_eq!(r.recv()?, "response");
+/// # anyhow::Ok(())
+/// ```
+pub fn oneshot<T>() -> (OneshotSender<T>, OneshotReceiver<T>) {
+    oneshot::new_oneshot()
+}
+
+/// Returns sender and async receiver of a oneshot channel for type T, the receiver is a future that resolves to the sent object.
+/// The receiver can be converted to the sync version of it with `to_sync`.
+/// # Examples
+///
+/// ```
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// use tokio::{spawn as co};
+///
+/// let (s, r) = kanal::oneshot_async();
+///
+/// co(async move {
+///       s.send("response")
+/// });
+///
+/// assert_eq!(r.await?, "response");
+/// anyhow::Ok(())
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub fn oneshot_async<T>() -> (OneshotSender<T>, OneshotAsyncReceiver<T>) {
+    let (s, r) = oneshot::new_oneshot();
+    (s, r.to_async())
+}
+
+/// Returns sender and sync receiver of a broadcast channel for type T, every receiver gets a clone of every sent object.
+/// The channel keeps the latest `capacity` objects, sending never blocks and receivers that fall behind get a `Lagged` error.
+/// Receivers can produce both async and sync versions via clone, clone_sync, and clone_async, and senders can subscribe new receivers.
+/// # Panics
+/// Panics if the capacity is zero.
+/// # Examples
+///
+/// ```
+/// use std::thread::spawn;
+///
+/// let (s, r) = kanal::broadcast(16);
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let r = r.clone();
+///         spawn(move || r.map(|v| v.unwrap()).sum::<u32>())
+///     })
+///     .collect();
+/// drop(r);
+/// for i in 1..=10 {
+///     s.send(i)?;
+/// }
+/// drop(s);
+/// for h in handles {
+///     assert_eq!(h.join().unwrap(), 55);
+/// }
+/// # anyhow::Ok(())
+/// ```
+pub fn broadcast<T>(capacity: usize) -> (BroadcastSender<T>, BroadcastReceiver<T>) {
+    broadcast::new_broadcast(capacity)
+}
+
+/// Returns sender and async receiver of a broadcast channel for type T, every receiver gets a clone of every sent object.
+/// The channel keeps the latest `capacity` objects, sending never blocks and receivers that fall behind get a `Lagged` error.
+/// # Panics
+/// Panics if the capacity is zero.
+/// # Examples
+///
+/// ```
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let (s, mut r) = kanal::broadcast_async(16);
+/// let mut r2 = r.clone();
+/// tokio::spawn(async move {
+///     s.send("hello!")
+/// });
+/// assert_eq!(r.recv().await?, "hello!");
+/// assert_eq!(r2.recv().await?, "hello!");
+/// anyhow::Ok(())
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub fn broadcast_async<T>(capacity: usize) -> (BroadcastSender<T>, BroadcastAsyncReceiver<T>) {
+    let (s, r) = broadcast::new_broadcast(capacity);
+    (s, r.into_async())
+}
+
+/// Returns sender and sync receiver of a watch channel for type T with the initial value, the channel keeps only the latest sent value.
+/// Receivers observe every change at least once, but changes that happen in between two waits are observed as one.
+/// Receivers can produce both async and sync versions via clone, clone_sync, and clone_async, and senders can subscribe new receivers.
+/// # Examples
+///
+/// ```
+/// use std::thread::spawn;
+///
+/// let (s, mut r) = kanal::watch("starting");
+/// let t = spawn(move || {
+///     while *r.borrow_and_update() != "healthy" {
+///         r.wait_changed()?;
+///     }
+///     anyhow::Ok(())
+/// });
+/// s.send("degraded")?;
+/// s.send("healthy")?;
+/// t.join().unwrap()?;
+/// # anyhow::Ok(())
+/// ```
+pub fn watch<T>(initial: T) -> (WatchSender<T>, WatchReceiver<T>) {
+    watch::new_watch(initial)
+}
+
+/// Returns sender and async receiver of a watch channel for type T with the initial value, the channel keeps only the latest sent value.
+/// Receivers observe every change at least once, but changes that happen in between two waits are observed as one.
+/// # Examples
+///
+/// ```
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let (s, mut r) = kanal::watch_async(0);
+/// let mut sync_receiver = r.clone_sync();
+/// s.send(1)?;
+/// r.changed().await?;
+/// assert_eq!(*r.borrow(), 1);
+/// sync_receiver.wait_changed()?;
+/// # anyhow::Ok(())
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub fn watch_async<T>(initial: T) -> (WatchSender<T>, WatchAsyncReceiver<T>) {
+    let (s, r) = watch::new_watch(initial);
+    (s, r.into_async())
+}
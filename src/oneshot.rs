@@ -0,0 +1,597 @@
+use std::{
+    cell::UnsafeCell,
+    fmt,
+    fmt::Debug,
+    mem::{size_of, MaybeUninit},
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+#[cfg(feature = "async")]
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use crate::{
+    backoff, pointer::KanalPtr, signal::*, ReceiveError, ReceiveErrorTimeout, SendError,
+    TryRecvError,
+};
+
+/// No object is sent and the receiver is not waiting
+const EMPTY: u8 = 0;
+/// The object is moved to the slot before the receiver started waiting
+const SENT: u8 = 1;
+/// The sync receiver registered its signal and waits for the object
+const WAITING: u8 = 2;
+/// The sender took the receiver signal or waker and finishes the handoff through it
+const CLAIMED: u8 = 3;
+/// The sender is dropped without sending or the receiver is dropped
+const CLOSED: u8 = 4;
+/// The receiver took the object
+const DONE: u8 = 5;
+/// The async receiver registered its waker and waits for the object
+#[cfg(feature = "async")]
+const POLLING: u8 = 6;
+
+/// Shared state of a oneshot channel, the handoff is lock-free and driven by the state transitions,
+///  each side only touches the slot or the waiter signal when the state gives it the ownership of them.
+struct OneshotInternal<T> {
+    state: AtomicU8,
+    /// The object that is sent before the receiver starts to wait, or sent to the async receiver
+    slot: UnsafeCell<MaybeUninit<T>>,
+    /// The signal of the waiting sync receiver
+    waiter: UnsafeCell<Signal<T>>,
+    /// The waker of the async receiver, the sender moves the object to the slot and wakes it,
+    ///  so the async receiver never waits for the sender to finish the handoff
+    #[cfg(feature = "async")]
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+// Safety: access to the slot and the signals is synchronized with the state transitions
+unsafe impl<T: Send> Send for OneshotInternal<T> {}
+// Safety: access to the slot and the signals is synchronized with the state transitions
+unsafe impl<T: Send> Sync for OneshotInternal<T> {}
+
+impl<T> OneshotInternal<T> {
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            state: AtomicU8::new(EMPTY),
+            slot: UnsafeCell::new(MaybeUninit::uninit()),
+            waiter: UnsafeCell::new(Signal::default()),
+            #[cfg(feature = "async")]
+            waker: UnsafeCell::new(None),
+        })
+    }
+
+    /// Moves the object out of the slot
+    /// Safety: it's only safe to call once when the slot is initialized and owned by the caller
+    #[inline(always)]
+    unsafe fn take_slot(&self) -> T {
+        std::ptr::read((*self.slot.get()).as_ptr())
+    }
+
+    /// Sends the object to the slot or directly to the waiting receiver
+    fn send(&self, mut data: T) -> Result<(), SendError<T>> {
+        let mut state = self.state.load(Ordering::Acquire);
+        loop {
+            match state {
+                EMPTY => {
+                    // Safety: the slot is not shared with the receiver in the empty state
+                    unsafe { (*self.slot.get()).write(data) };
+                    match self.state.compare_exchange(
+                        EMPTY,
+                        SENT,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) => return Ok(()),
+                        Err(s) => {
+                            // Safety: the object is not published, take it back
+                            data = unsafe { self.take_slot() };
+                            state = s;
+                        }
+                    }
+                }
+                WAITING => {
+                    match self.state.compare_exchange(
+                        WAITING,
+                        CLAIMED,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) => {
+                            // Safety: the signal is owned by the sender after claiming it
+                            unsafe { (*self.waiter.get()).send(data) };
+                            return Ok(());
+                        }
+                        // the receiver withdrew its signal or is dropped
+                        Err(s) => state = s,
+                    }
+                }
+                #[cfg(feature = "async")]
+                POLLING => {
+                    match self.state.compare_exchange(
+                        POLLING,
+                        CLAIMED,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) => {
+                            // Safety: the slot and the waker are owned by the sender after claiming them
+                            let waker = unsafe {
+                                (*self.slot.get()).write(data);
+                                (*self.waker.get()).take()
+                            };
+                            if self
+                                .state
+                                .compare_exchange(
+                                    CLAIMED,
+                                    SENT,
+                                    Ordering::AcqRel,
+                                    Ordering::Acquire,
+                                )
+                                .is_err()
+                            {
+                                // the receiver is dropped in the middle of the handoff, take the object back
+                                // Safety: the object is not published
+                                return Err(SendError::ReceiveClosed(unsafe { self.take_slot() }));
+                            }
+                            if let Some(waker) = waker {
+                                waker.wake();
+                            }
+                            return Ok(());
+                        }
+                        // the receiver withdrew its waker or is dropped
+                        Err(s) => state = s,
+                    }
+                }
+                _ => return Err(SendError::ReceiveClosed(data)),
+            }
+        }
+    }
+
+    /// Closes the send side without sending, a waiting receiver gets terminated
+    fn close_send(&self) {
+        let mut state = self.state.load(Ordering::Acquire);
+        loop {
+            match state {
+                EMPTY => {
+                    match self.state.compare_exchange(
+                        EMPTY,
+                        CLOSED,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) => return,
+                        Err(s) => state = s,
+                    }
+                }
+                WAITING => {
+                    match self.state.compare_exchange(
+                        WAITING,
+                        CLAIMED,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) => {
+                            // Safety: the signal is owned by the sender after claiming it
+                            unsafe { (*self.waiter.get()).terminate() };
+                            return;
+                        }
+                        Err(s) => state = s,
+                    }
+                }
+                #[cfg(feature = "async")]
+                POLLING => {
+                    match self.state.compare_exchange(
+                        POLLING,
+                        CLAIMED,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) => {
+                            // Safety: the waker is owned by the sender after claiming it
+                            let waker = unsafe { (*self.waker.get()).take() };
+                            // the receiver may be dropped in the middle of it, the channel is closed either way
+                            self.state.store(CLOSED, Ordering::Release);
+                            if let Some(waker) = waker {
+                                waker.wake();
+                            }
+                            return;
+                        }
+                        Err(s) => state = s,
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Closes the receive side and drops the object if it's sent but not received.
+    /// The receiver should not be waiting when this function is called, if the sender claimed the waker of the async receiver,
+    ///  the sender takes its object back instead.
+    fn close_recv(&self) {
+        let mut state = self.state.load(Ordering::Acquire);
+        loop {
+            match state {
+                EMPTY | SENT | CLAIMED => {
+                    match self.state.compare_exchange(
+                        state,
+                        CLOSED,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) => {
+                            if state == SENT {
+                                // Safety: the object is sent and nobody is going to receive it
+                                drop(unsafe { self.take_slot() });
+                            }
+                            return;
+                        }
+                        Err(s) => state = s,
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Tries to receive the object without waiting
+    fn try_recv(&self) -> Result<T, TryRecvError> {
+        match self.state.load(Ordering::Acquire) {
+            SENT => {
+                self.state.store(DONE, Ordering::Relaxed);
+                // Safety: the object is sent, and the sender is gone
+                Ok(unsafe { self.take_slot() })
+            }
+            // the sender is in the middle of the handoff to a former async receiver
+            EMPTY | CLAIMED => Err(TryRecvError::Empty),
+            CLOSED => Err(TryRecvError::SendClosed),
+            _ => Err(TryRecvError::Closed),
+        }
+    }
+
+    /// Waits synchronously for the object until the deadline
+    fn recv_until(&self, deadline: Option<Instant>) -> Result<T, ReceiveErrorTimeout> {
+        let mut ret = MaybeUninit::<T>::uninit();
+        let _ret_address_holder = &ret;
+        let sig = SyncSignal::new(KanalPtr::new_write_address_ptr(ret.as_mut_ptr()));
+        let _sig_address_holder = &sig;
+        let mut state = self.state.load(Ordering::Acquire);
+        loop {
+            match state {
+                EMPTY => {
+                    // Safety: the waiter is not shared with the sender in the empty state
+                    unsafe { *self.waiter.get() = sig.as_signal() };
+                    match self.state.compare_exchange(
+                        EMPTY,
+                        WAITING,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) => break,
+                        Err(s) => state = s,
+                    }
+                }
+                SENT => {
+                    self.state.store(DONE, Ordering::Relaxed);
+                    // Safety: the object is sent, and the sender is gone
+                    return Ok(unsafe { self.take_slot() });
+                }
+                CLAIMED => {
+                    // the sender is in the middle of the handoff to a former async receiver, it moves the object to the slot shortly
+                    backoff::yield_now();
+                    state = self.state.load(Ordering::Acquire);
+                }
+                CLOSED => return Err(ReceiveErrorTimeout::SendClosed),
+                DONE => return Err(ReceiveErrorTimeout::Closed),
+                _ => unreachable!("bug: sync oneshot receiver is already waiting"),
+            }
+        }
+        let received = match deadline {
+            None => sig.wait(),
+            Some(deadline) => {
+                if sig.wait_timeout(deadline) {
+                    true
+                } else if sig.is_terminated() {
+                    false
+                } else if self
+                    .state
+                    .compare_exchange(WAITING, EMPTY, Ordering::AcqRel, Ordering::Acquire)
+                    .is_ok()
+                {
+                    return Err(ReceiveErrorTimeout::Timeout);
+                } else {
+                    // the sender claimed the signal, wait for the handoff result
+                    sig.wait()
+                }
+            }
+        };
+        if !received {
+            self.state.store(CLOSED, Ordering::Relaxed);
+            return Err(ReceiveErrorTimeout::SendClosed);
+        }
+        self.state.store(DONE, Ordering::Relaxed);
+        // Safety: it's safe to assume init as data is forgotten on another side
+        if size_of::<T>() > size_of::<*mut T>() {
+            Ok(unsafe { ret.assume_init() })
+        } else {
+            Ok(unsafe { sig.assume_init() })
+        }
+    }
+
+    /// Withdraws the waker of the async receiver, if the sender already claimed it the handoff is left to the sender
+    #[cfg(feature = "async")]
+    fn settle_async(&self) {
+        let _ = self
+            .state
+            .compare_exchange(POLLING, EMPTY, Ordering::AcqRel, Ordering::Acquire);
+    }
+}
+
+/// Sending side of a oneshot channel, it's consumed by sending the object.
+/// Sending never blocks, so the same sender is used with both sync and async receivers.
+/// Dropping the sender without sending closes the channel for the receiver.
+/// # Examples
+///
+/// ```
+/// let (s, r) = kanal::oneshot();
+/// s.send("Hello")?;
+/// assert_eq!(r.recv()?, "Hello");
+/// # anyhow::Ok(())
+/// ```
+pub struct OneshotSender<T> {
+    internal: Option<Arc<OneshotInternal<T>>>,
+}
+
+impl<T> OneshotSender<T> {
+    /// Sends the object to the receiver without blocking, the object is handed back inside the error if the receiver is dropped
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = kanal::oneshot();
+    /// drop(r);
+    /// assert_eq!(s.send(1), Err(kanal::SendError::ReceiveClosed(1)));
+    /// ```
+    pub fn send(mut self, data: T) -> Result<(), SendError<T>> {
+        self.internal.take().unwrap().send(data)
+    }
+    /// Returns whether the receiver is dropped or not
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = kanal::oneshot::<u64>();
+    /// assert!(!s.is_closed());
+    /// drop(r);
+    /// assert!(s.is_closed());
+    /// ```
+    pub fn is_closed(&self) -> bool {
+        self.internal
+            .as_ref()
+            .unwrap()
+            .state
+            .load(Ordering::Relaxed)
+            == CLOSED
+    }
+}
+
+impl<T> Drop for OneshotSender<T> {
+    fn drop(&mut self) {
+        if let Some(internal) = self.internal.take() {
+            internal.close_send();
+        }
+    }
+}
+
+impl<T> Debug for OneshotSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OneshotSender {{ .. }}")
+    }
+}
+
+/// Receiving side of a oneshot channel in sync mode.
+/// # Examples
+///
+/// ```
+/// # use std::thread::spawn;
+/// let (s, r) = kanal::oneshot();
+/// spawn(move || s.send(1));
+/// assert_eq!(r.recv()?, 1);
+/// # anyhow::Ok(())
+/// ```
+pub struct OneshotReceiver<T> {
+    internal: Arc<OneshotInternal<T>>,
+}
+
+impl<T> OneshotReceiver<T> {
+    /// Waits for the object, returns `ReceiveError::SendClosed` if the sender is dropped without sending
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = kanal::oneshot::<u64>();
+    /// drop(s);
+    /// assert_eq!(r.recv(), Err(kanal::ReceiveError::SendClosed));
+    /// ```
+    pub fn recv(self) -> Result<T, ReceiveError> {
+        self.internal.recv_until(None).map_err(|err| match err {
+            ReceiveErrorTimeout::Closed => ReceiveError::Closed,
+            ReceiveErrorTimeout::SendClosed => ReceiveError::SendClosed,
+            ReceiveErrorTimeout::Timeout => unreachable!("bug: receive without deadline timed out"),
+        })
+    }
+    /// Waits for the object for the duration, the receiver stays usable after a timeout
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// let (s, mut r) = kanal::oneshot();
+    /// assert_eq!(
+    ///     r.recv_timeout(Duration::from_millis(10)),
+    ///     Err(kanal::ReceiveErrorTimeout::Timeout)
+    /// );
+    /// s.send(1)?;
+    /// assert_eq!(r.recv_timeout(Duration::from_millis(10))?, 1);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn recv_timeout(&mut self, duration: Duration) -> Result<T, ReceiveErrorTimeout> {
+        self.internal
+            .recv_until(Some(Instant::now().checked_add(duration).unwrap()))
+    }
+    /// Tries receiving the object without waiting
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, mut r) = kanal::oneshot();
+    /// assert_eq!(r.try_recv(), Err(kanal::TryRecvError::Empty));
+    /// s.send(1)?;
+    /// assert_eq!(r.try_recv()?, 1);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        self.internal.try_recv()
+    }
+    /// Converts the receiver to the async version of it
+    #[cfg(feature = "async")]
+    pub fn to_async(self) -> OneshotAsyncReceiver<T> {
+        let this = std::mem::ManuallyDrop::new(self);
+        OneshotAsyncReceiver {
+            // Safety: self is not going to be dropped, so the internal is moved out of it
+            internal: unsafe { std::ptr::read(&this.internal) },
+        }
+    }
+}
+
+impl<T> Drop for OneshotReceiver<T> {
+    fn drop(&mut self) {
+        self.internal.close_recv();
+    }
+}
+
+impl<T> Debug for OneshotReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OneshotReceiver {{ .. }}")
+    }
+}
+
+/// Receiving side of a oneshot channel in async mode, it's a future that resolves to the sent object.
+/// # Examples
+///
+/// ```
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let (s, r) = kanal::oneshot_async();
+/// tokio::spawn(async move { s.send(1) });
+/// assert_eq!(r.await?, 1);
+/// # anyhow::Ok(())
+/// # });
+/// ```
+#[cfg(feature = "async")]
+#[must_use = "futures do nothing unless you .await or poll them"]
+pub struct OneshotAsyncReceiver<T> {
+    internal: Arc<OneshotInternal<T>>,
+}
+
+#[cfg(feature = "async")]
+impl<T> OneshotAsyncReceiver<T> {
+    /// Tries receiving the object without waiting
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        self.internal.settle_async();
+        self.internal.try_recv()
+    }
+    /// Converts the receiver to the sync version of it
+    pub fn to_sync(self) -> OneshotReceiver<T> {
+        self.internal.settle_async();
+        let this = std::mem::ManuallyDrop::new(self);
+        OneshotReceiver {
+            // Safety: self is not going to be dropped, so the internal is moved out of it
+            internal: unsafe { std::ptr::read(&this.internal) },
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Future for OneshotAsyncReceiver<T> {
+    type Output = Result<T, ReceiveError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let internal = &*self.internal;
+        let mut state = internal.state.load(Ordering::Acquire);
+        loop {
+            match state {
+                EMPTY => {
+                    // Safety: the waker is not shared with the sender in the empty state
+                    unsafe { *internal.waker.get() = Some(cx.waker().clone()) };
+                    match internal.state.compare_exchange(
+                        EMPTY,
+                        POLLING,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) => return Poll::Pending,
+                        Err(s) => state = s,
+                    }
+                }
+                POLLING => {
+                    // Safety: the receiver owns the waker, the sender only touches it after claiming it
+                    if unsafe { (*internal.waker.get()).as_ref() }
+                        .is_some_and(|waker| waker.will_wake(cx.waker()))
+                    {
+                        return Poll::Pending;
+                    }
+                    // the waker is changed, withdraw it to register the new one
+                    match internal.state.compare_exchange(
+                        POLLING,
+                        EMPTY,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) => state = EMPTY,
+                        Err(s) => state = s,
+                    }
+                }
+                CLAIMED => {
+                    // the sender took the waker and moves the object to the slot shortly, it may hold the waker of
+                    //  another task, so the task is polled again instead of waiting for the sender
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+                SENT => {
+                    internal.state.store(DONE, Ordering::Relaxed);
+                    // Safety: the object is sent, and the sender is gone
+                    return Poll::Ready(Ok(unsafe { internal.take_slot() }));
+                }
+                CLOSED => return Poll::Ready(Err(ReceiveError::SendClosed)),
+                _ => return Poll::Ready(Err(ReceiveError::Closed)),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Drop for OneshotAsyncReceiver<T> {
+    fn drop(&mut self) {
+        self.internal.settle_async();
+        self.internal.close_recv();
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Debug for OneshotAsyncReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OneshotAsyncReceiver {{ .. }}")
+    }
+}
+
+/// Creates a oneshot channel in sync mode, it carries exactly one object from the sender to the receiver
+pub(crate) fn new_oneshot<T>() -> (OneshotSender<T>, OneshotReceiver<T>) {
+    let internal = OneshotInternal::new();
+    (
+        OneshotSender {
+            internal: Some(internal.clone()),
+        },
+        OneshotReceiver { internal },
+    )
+}
@@ -0,0 +1,354 @@
+use std::{cmp::Ordering, collections::VecDeque, fmt};
+
+#[cfg(feature = "async")]
+use crate::{AsyncReceiver, AsyncSender};
+use crate::{Builder, Receiver, Sender};
+
+/// Ordering of a priority channel, objects that compare greater are received first,
+///  and objects with equal priority are received in the order they are sent.
+/// # Examples
+///
+/// ```
+/// // lower numbers first, and an object is received after at most 8 other receives
+/// let (s, r) = kanal::PriorityOrder::by(|a: &u32, b: &u32| b.cmp(a))
+///     .aging(8)
+///     .unbounded();
+/// s.send(3)?;
+/// s.send(1)?;
+/// s.send(2)?;
+/// assert_eq!(r.recv()?, 1);
+/// assert_eq!(r.recv()?, 2);
+/// assert_eq!(r.recv()?, 3);
+/// # anyhow::Ok(())
+/// ```
+pub struct PriorityOrder<T> {
+    cmp: fn(&T, &T) -> Ordering,
+    aging: Option<u64>,
+}
+
+impl<T: Ord> PriorityOrder<T> {
+    /// Returns the ordering that receives the greatest object first based on `Ord` of T
+    pub fn natural() -> Self {
+        Self::by(T::cmp)
+    }
+}
+
+impl<T: Ord> Default for PriorityOrder<T> {
+    fn default() -> Self {
+        Self::natural()
+    }
+}
+
+impl<T> PriorityOrder<T> {
+    /// Returns the ordering that receives the greatest object first based on the provided compare function
+    pub fn by(cmp: fn(&T, &T) -> Ordering) -> Self {
+        Self { cmp, aging: None }
+    }
+    /// Enables aging, a buffered object is received next regardless of its priority
+    ///  after `receives` other objects are received since it was buffered,
+    ///  so low-priority objects are not starved forever.
+    pub fn aging(mut self, receives: usize) -> Self {
+        self.aging = Some(receives as u64);
+        self
+    }
+    /// Returns bounded, sync sender and receiver of a priority channel with this ordering, see [`priority_bounded`](crate::priority_bounded)
+    pub fn bounded(self, size: usize) -> (Sender<T>, Receiver<T>) {
+        Builder::new().bounded(size).priority(self).build()
+    }
+    /// Returns bounded, async sender and receiver of a priority channel with this ordering, see [`priority_bounded_async`](crate::priority_bounded_async)
+    #[cfg(feature = "async")]
+    pub fn bounded_async(self, size: usize) -> (AsyncSender<T>, AsyncReceiver<T>) {
+        Builder::new().bounded(size).priority(self).build_async()
+    }
+    /// Returns unbounded, sync sender and receiver of a priority channel with this ordering, see [`priority_unbounded`](crate::priority_unbounded)
+    pub fn unbounded(self) -> (Sender<T>, Receiver<T>) {
+        Builder::new().priority(self).build()
+    }
+    /// Returns unbounded, async sender and receiver of a priority channel with this ordering, see [`priority_unbounded_async`](crate::priority_unbounded_async)
+    #[cfg(feature = "async")]
+    pub fn unbounded_async(self) -> (AsyncSender<T>, AsyncReceiver<T>) {
+        Builder::new().priority(self).build_async()
+    }
+}
+
+impl<T> Clone for PriorityOrder<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PriorityOrder<T> {}
+
+impl<T> fmt::Debug for PriorityOrder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PriorityOrder")
+            .field("aging", &self.aging)
+            .finish()
+    }
+}
+
+/// Behavior of a bounded channel when a sender finds the queue full
+/// # Examples
+///
+/// ```
+/// let (s, r) = kanal::bounded_with_overflow(2, kanal::OverflowPolicy::DropOldest);
+/// for i in 0..5 {
+///     s.send(i)?;
+/// }
+/// assert_eq!(r.evicted_count(), 3);
+/// assert_eq!(r.recv()?, 3);
+/// assert_eq!(r.recv()?, 4);
+/// # anyhow::Ok(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Senders wait on the waitlist until there is room in the queue, it's the behavior of regular channels
+    #[default]
+    Block,
+    /// The oldest buffered object is evicted to make room for the new one, senders never wait
+    DropOldest,
+    /// The new object is evicted and the queue is kept as is, senders never wait
+    DropNewest,
+}
+
+/// Buffer of the channel, it's a FIFO queue for regular channels and a priority queue for priority channels
+pub(crate) enum Queue<T> {
+    Fifo(VecDeque<T>),
+    Priority(PriorityQueue<T>),
+}
+
+impl<T> Queue<T> {
+    /// Returns a FIFO queue with the required capacity
+    pub(crate) fn fifo(capacity: usize) -> Self {
+        Queue::Fifo(VecDeque::with_capacity(capacity))
+    }
+    /// Returns count of buffered objects
+    #[inline(always)]
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Queue::Fifo(q) => q.len(),
+            Queue::Priority(q) => q.heap.len(),
+        }
+    }
+    /// Returns whether the queue is empty or not
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Pushes the object to the queue
+    #[inline(always)]
+    pub(crate) fn push_back(&mut self, data: T) {
+        match self {
+            Queue::Fifo(q) => q.push_back(data),
+            Queue::Priority(q) => q.push(data),
+        }
+    }
+    /// Pops the next object that should be received
+    #[inline(always)]
+    pub(crate) fn pop_front(&mut self) -> Option<T> {
+        match self {
+            Queue::Fifo(q) => q.pop_front(),
+            Queue::Priority(q) => q.pop(),
+        }
+    }
+    /// Pops the object that is buffered before the others, it's used to evict objects on overflow
+    #[inline(always)]
+    pub(crate) fn pop_oldest(&mut self) -> Option<T> {
+        match self {
+            Queue::Fifo(q) => q.pop_front(),
+            Queue::Priority(q) => q.pop_oldest(),
+        }
+    }
+    /// Takes all buffered objects and leaves an empty queue of the same kind in place,
+    ///  so objects can be dropped or handed back outside of the channel lock
+    pub(crate) fn take(&mut self) -> Self {
+        match self {
+            Queue::Fifo(q) => Queue::Fifo(std::mem::take(q)),
+            Queue::Priority(q) => {
+                let empty = PriorityQueue::new(q.order, 0);
+                Queue::Priority(std::mem::replace(q, empty))
+            }
+        }
+    }
+}
+
+/// Buffered object of the priority queue
+struct Node<T> {
+    data: T,
+    /// Arrival sequence of the object, it breaks ties of equal priorities
+    seq: u64,
+    /// Count of receives from the queue when the object was pushed, it's used for aging
+    enqueued_at: u64,
+    /// Position of the object in the heap
+    pos: usize,
+    /// Slab index of the object that is buffered right before this one, or `NIL`
+    older: usize,
+    /// Slab index of the object that is buffered right after this one, or `NIL`
+    newer: usize,
+}
+
+/// Slab index that links to no object
+const NIL: usize = usize::MAX;
+
+/// Binary heap of buffered objects, objects live in a slab so they can be removed from the middle of the heap
+///  when they get too old or evicted, and they are linked in arrival order so the oldest one is found in constant time.
+pub(crate) struct PriorityQueue<T> {
+    order: PriorityOrder<T>,
+    slab: Vec<Option<Node<T>>>,
+    /// Free slots of the slab
+    free: Vec<usize>,
+    /// Slab indexes in heap order
+    heap: Vec<usize>,
+    /// Slab index of the oldest buffered object, or `NIL`
+    oldest: usize,
+    /// Slab index of the newest buffered object, or `NIL`
+    newest: usize,
+    next_seq: u64,
+    /// Count of receives from the queue
+    popped: u64,
+}
+
+impl<T> PriorityQueue<T> {
+    pub(crate) fn new(order: PriorityOrder<T>, capacity: usize) -> Self {
+        Self {
+            order,
+            slab: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            heap: Vec::with_capacity(capacity),
+            oldest: NIL,
+            newest: NIL,
+            next_seq: 0,
+            popped: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn node(&self, heap_pos: usize) -> &Node<T> {
+        self.slab[self.heap[heap_pos]].as_ref().unwrap()
+    }
+
+    /// Returns whether the object at heap position `a` should be received before the one at `b`
+    #[inline(always)]
+    fn precedes(&self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.node(a), self.node(b));
+        match (self.order.cmp)(&a.data, &b.data) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => a.seq < b.seq,
+        }
+    }
+
+    #[inline(always)]
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.slab[self.heap[a]].as_mut().unwrap().pos = a;
+        self.slab[self.heap[b]].as_mut().unwrap().pos = b;
+    }
+
+    fn sift_up(&mut self, mut pos: usize) {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if !self.precedes(pos, parent) {
+                break;
+            }
+            self.swap(pos, parent);
+            pos = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut pos: usize) {
+        loop {
+            let mut first = pos;
+            for child in [2 * pos + 1, 2 * pos + 2] {
+                if child < self.heap.len() && self.precedes(child, first) {
+                    first = child;
+                }
+            }
+            if first == pos {
+                break;
+            }
+            self.swap(pos, first);
+            pos = first;
+        }
+    }
+
+    fn push(&mut self, data: T) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let node = Node {
+            data,
+            seq,
+            enqueued_at: self.popped,
+            pos: self.heap.len(),
+            older: self.newest,
+            newer: NIL,
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slab[index] = Some(node);
+                index
+            }
+            None => {
+                self.slab.push(Some(node));
+                self.slab.len() - 1
+            }
+        };
+        self.heap.push(index);
+        match self.newest {
+            NIL => self.oldest = index,
+            newest => self.slab[newest].as_mut().unwrap().newer = index,
+        }
+        self.newest = index;
+        self.sift_up(self.heap.len() - 1);
+    }
+
+    /// Returns heap position of the oldest object if it waited long enough to be received regardless of its priority
+    fn aged(&self) -> Option<usize> {
+        let aging = self.order.aging?;
+        if self.oldest == NIL {
+            return None;
+        }
+        let node = self.slab[self.oldest].as_ref().unwrap();
+        (self.popped - node.enqueued_at >= aging).then_some(node.pos)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let pos = self.aged().unwrap_or(0);
+        self.popped += 1;
+        Some(self.remove(pos))
+    }
+
+    /// Removes the object that is buffered before the others regardless of its priority
+    fn pop_oldest(&mut self) -> Option<T> {
+        if self.oldest == NIL {
+            return None;
+        }
+        let pos = self.slab[self.oldest].as_ref().unwrap().pos;
+        Some(self.remove(pos))
+    }
+
+    /// Removes the object at the heap position
+    fn remove(&mut self, pos: usize) -> T {
+        let last = self.heap.len() - 1;
+        self.swap(pos, last);
+        let index = self.heap.pop().unwrap();
+        if pos < self.heap.len() {
+            self.sift_down(pos);
+            self.sift_up(pos);
+        }
+        self.free.push(index);
+        let node = self.slab[index].take().unwrap();
+        match node.older {
+            NIL => self.oldest = node.newer,
+            older => self.slab[older].as_mut().unwrap().newer = node.newer,
+        }
+        match node.newer {
+            NIL => self.newest = node.older,
+            newer => self.slab[newer].as_mut().unwrap().older = node.older,
+        }
+        node.data
+    }
+}
@@ -0,0 +1,464 @@
+use std::{
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::backoff;
+use crate::internal::acquire_internal;
+use crate::pointer::KanalPtr;
+use crate::signal::{SelectEntry, SelectSignal, SyncSignal};
+#[cfg(feature = "async")]
+use crate::{AsyncReceiver, RecvAnyFuture};
+use crate::{ReceiveError, Receiver, SendError, Sender, TryRecvError, TrySendError};
+
+/// Operation of a select, it's implemented for receive and send operations of channels of any type
+trait SelectArm {
+    /// Tries to complete the operation without waiting, returns true if it's completed with an object or an error
+    fn try_complete(&mut self) -> bool;
+    /// Adds the select signal to the waitlist of the channel, returns false without adding it
+    ///  if the operation can complete without waiting
+    fn register(&self, sig: *const SyncSignal<()>) -> bool;
+    /// Removes the select signal from the waitlist of the channel if it's still there
+    fn unregister(&self);
+    /// Returns address of the channel internal and whether the operation is a send
+    fn key(&self) -> (*const (), bool);
+    /// Returns pointer to the result of the completed operation
+    fn result(&mut self) -> *mut ();
+}
+
+struct RecvArm<'a, T> {
+    receiver: &'a Receiver<T>,
+    // arms are boxed, so the entry stays in place while it's linked in the waitlist
+    entry: SelectEntry<T>,
+    result: Option<Result<T, ReceiveError>>,
+}
+
+impl<T> SelectArm for RecvArm<'_, T> {
+    fn try_complete(&mut self) -> bool {
+        self.result = match self.receiver.try_recv() {
+            Ok(data) => Some(Ok(data)),
+            Err(TryRecvError::Empty | TryRecvError::Contended) => return false,
+            Err(TryRecvError::Closed) => Some(Err(ReceiveError::Closed)),
+            Err(TryRecvError::SendClosed) => Some(Err(ReceiveError::SendClosed)),
+        };
+        true
+    }
+    fn register(&self, sig: *const SyncSignal<()>) -> bool {
+        let mut internal = acquire_internal(&self.receiver.internal);
+        if internal.recv_ready() {
+            return false;
+        }
+        // Safety: the entry is not linked as the arm is unregistered before it's registered again
+        internal.push_recv(unsafe { self.entry.bind(SelectSignal::Sync(sig)) });
+        true
+    }
+    fn unregister(&self) {
+        acquire_internal(&self.receiver.internal).cancel_recv_signal(self.entry.as_signal());
+    }
+    fn key(&self) -> (*const (), bool) {
+        (Arc::as_ptr(&self.receiver.internal) as *const (), false)
+    }
+    fn result(&mut self) -> *mut () {
+        &mut self.result as *mut _ as *mut ()
+    }
+}
+
+struct SendArm<'a, T> {
+    sender: &'a Sender<T>,
+    // arms are boxed, so the entry stays in place while it's linked in the waitlist
+    entry: SelectEntry<T>,
+    data: Option<T>,
+    result: Option<Result<(), SendError<T>>>,
+}
+
+impl<T> SelectArm for SendArm<'_, T> {
+    fn try_complete(&mut self) -> bool {
+        let data = self.data.take().unwrap();
+        self.result = match self.sender.try_send(data) {
+            Ok(()) => Some(Ok(())),
+            Err(TrySendError::Full(data) | TrySendError::Contended(data)) => {
+                self.data = Some(data);
+                return false;
+            }
+            Err(TrySendError::Closed(data)) => Some(Err(SendError::Closed(data))),
+            Err(TrySendError::ReceiveClosed(data)) => Some(Err(SendError::ReceiveClosed(data))),
+        };
+        true
+    }
+    fn register(&self, sig: *const SyncSignal<()>) -> bool {
+        let mut internal = acquire_internal(&self.sender.internal);
+        if internal.send_ready() {
+            return false;
+        }
+        // Safety: the entry is not linked as the arm is unregistered before it's registered again
+        internal.push_send(unsafe { self.entry.bind(SelectSignal::Sync(sig)) });
+        true
+    }
+    fn unregister(&self) {
+        acquire_internal(&self.sender.internal).cancel_send_signal(self.entry.as_signal());
+    }
+    fn key(&self) -> (*const (), bool) {
+        (Arc::as_ptr(&self.sender.internal) as *const (), true)
+    }
+    fn result(&mut self) -> *mut () {
+        &mut self.result as *mut _ as *mut ()
+    }
+}
+
+/// Select waits on receive and send operations of several channels and completes exactly one of them,
+///  operations that are not selected have no effect on their channels and their objects are dropped with the select.
+///  See [`select!`](crate::select) for a more convenient syntax.
+///
+/// Selects only wake up on their channels and retry their operations, so a select never pairs with
+///  another select on the opposite side of a zero capacity channel, at least one side of such channels
+///  needs to be a regular send or receive.
+/// # Examples
+///
+/// ```
+/// let (s1, r1) = kanal::bounded::<u64>(0);
+/// let (s2, r2) = kanal::bounded::<String>(0);
+/// std::thread::spawn(move || s2.send("hello".to_string()));
+///
+/// let mut select = kanal::Select::new();
+/// let first = select.recv(&r1);
+/// let second = select.recv(&r2);
+/// let op = select.select();
+/// assert_eq!(op.index(), second);
+/// assert_eq!(op.recv(&r2)?, "hello");
+/// # let _ = (first, s1);
+/// # anyhow::Ok(())
+/// ```
+pub struct Select<'a> {
+    arms: Vec<Box<dyn SelectArm + 'a>>,
+}
+
+impl<'a> Select<'a> {
+    /// Returns an empty select
+    pub fn new() -> Self {
+        Self { arms: Vec::new() }
+    }
+    /// Adds a receive operation on the receiver to the select and returns index of the operation
+    pub fn recv<T>(&mut self, receiver: &'a Receiver<T>) -> usize {
+        self.arms.push(Box::new(RecvArm {
+            receiver,
+            entry: SelectEntry::new(),
+            result: None,
+        }));
+        self.arms.len() - 1
+    }
+    /// Adds an operation that sends the object to the sender to the select and returns index of the operation
+    pub fn send<T>(&mut self, sender: &'a Sender<T>, data: T) -> usize {
+        self.arms.push(Box::new(SendArm {
+            sender,
+            entry: SelectEntry::new(),
+            data: Some(data),
+            result: None,
+        }));
+        self.arms.len() - 1
+    }
+    /// Waits until one of the operations completes and returns it
+    /// # Panics
+    /// Panics if the select has no operations, as it would wait forever.
+    pub fn select(mut self) -> SelectedOperation<'a> {
+        assert!(!self.arms.is_empty(), "select without operations");
+        let index = self.run(None, true).unwrap();
+        self.selected(index)
+    }
+    /// Completes one of the operations if it's possible without waiting, it returns None otherwise,
+    ///  it's the select with a default operation
+    pub fn try_select(mut self) -> Option<SelectedOperation<'a>> {
+        let index = self.run(None, false)?;
+        Some(self.selected(index))
+    }
+    /// Waits until one of the operations completes or the timeout passes, it returns None on timeout
+    pub fn select_timeout(self, duration: Duration) -> Option<SelectedOperation<'a>> {
+        self.select_deadline(Instant::now() + duration)
+    }
+    /// Waits until one of the operations completes or the deadline is reached, it returns None on timeout
+    pub fn select_deadline(mut self, deadline: Instant) -> Option<SelectedOperation<'a>> {
+        let index = self.run(Some(deadline), true)?;
+        Some(self.selected(index))
+    }
+    fn selected(mut self, index: usize) -> SelectedOperation<'a> {
+        SelectedOperation {
+            index,
+            arm: self.arms.swap_remove(index),
+        }
+    }
+    /// Completes one of the operations and returns its index, it tries operations from a random one
+    ///  to keep the select fair among its channels.
+    fn run(&mut self, deadline: Option<Instant>, block: bool) -> Option<usize> {
+        let len = self.arms.len();
+        let start = if len == 0 {
+            0
+        } else {
+            backoff::random_u32() as usize % len
+        };
+        loop {
+            for index in (start..len).chain(0..start) {
+                if self.arms[index].try_complete() {
+                    return Some(index);
+                }
+            }
+            if !block {
+                return None;
+            }
+            if let Some(deadline) = deadline {
+                if Instant::now() >= deadline {
+                    return None;
+                }
+            }
+            // one signal is shared among the waitlists of all operations, peers only notify it,
+            //  and the operations are retried after the notification.
+            let sig = SyncSignal::<()>::new(KanalPtr::default());
+            let ptr = &sig as *const SyncSignal<()>;
+            let registered = self.arms.iter().take_while(|arm| arm.register(ptr)).count();
+            if registered == len {
+                match deadline {
+                    Some(deadline) => {
+                        sig.wait_timeout(deadline);
+                    }
+                    None => {
+                        sig.wait();
+                    }
+                }
+            }
+            // the signal must be removed from all waitlists before it's released
+            for arm in &self.arms[..registered] {
+                arm.unregister();
+            }
+        }
+    }
+}
+
+impl Default for Select<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Select<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Select")
+            .field("operations", &self.arms.len())
+            .finish()
+    }
+}
+
+/// Completed operation of a [`Select`], its result is taken with the endpoint that is used for the operation
+pub struct SelectedOperation<'a> {
+    index: usize,
+    arm: Box<dyn SelectArm + 'a>,
+}
+
+impl SelectedOperation<'_> {
+    /// Returns index of the completed operation
+    pub fn index(&self) -> usize {
+        self.index
+    }
+    /// Returns result of the completed receive operation
+    /// # Panics
+    /// Panics if the completed operation is not a receive operation on the receiver's channel.
+    pub fn recv<T>(mut self, receiver: &Receiver<T>) -> Result<T, ReceiveError> {
+        assert!(
+            self.arm.key() == (Arc::as_ptr(&receiver.internal) as *const (), false),
+            "selected operation is not a receive on the provided receiver"
+        );
+        // Safety: the operation is done on the same channel, so its result has the same type
+        unsafe {
+            (*(self.arm.result() as *mut Option<Result<T, ReceiveError>>))
+                .take()
+                .unwrap()
+        }
+    }
+    /// Returns result of the completed send operation, the object is returned in the error if the channel is closed
+    /// # Panics
+    /// Panics if the completed operation is not a send operation on the sender's channel.
+    pub fn send<T>(mut self, sender: &Sender<T>) -> Result<(), SendError<T>> {
+        assert!(
+            self.arm.key() == (Arc::as_ptr(&sender.internal) as *const (), true),
+            "selected operation is not a send on the provided sender"
+        );
+        // Safety: the operation is done on the same channel, so its result has the same type
+        unsafe {
+            (*(self.arm.result() as *mut Option<Result<(), SendError<T>>>))
+                .take()
+                .unwrap()
+        }
+    }
+}
+
+impl fmt::Debug for SelectedOperation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectedOperation")
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// Receives an object from any of the async receivers, the returned future resolves to the index of the
+///  receiver in the slice and the result of its receive. Receivers are tried from a random one on every poll
+///  to keep the receive fair among the channels. Closed receivers are skipped while any other receiver is open,
+///  and once all of them are closed the future completes with the error of the first one in the slice.
+///
+/// The future only waits for notifications of the channels and objects are never moved to it before
+///  it completes, so dropping it never loses an object or waits for a peer.
+/// # Panics
+/// Panics if the slice is empty.
+/// # Examples
+///
+/// ```
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let (s1, r1) = kanal::bounded_async::<u64>(0);
+/// let (s2, r2) = kanal::bounded_async::<u64>(0);
+/// tokio::spawn(async move { s2.send(2).await });
+/// let (index, res) = kanal::recv_any(&[&r1, &r2]).await;
+/// assert_eq!(index, 1);
+/// assert_eq!(res?, 2);
+/// # drop(s1);
+/// # anyhow::Ok(())
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub fn recv_any<'a, T>(receivers: &'a [&'a AsyncReceiver<T>]) -> RecvAnyFuture<'a, T> {
+    assert!(!receivers.is_empty(), "recv_any without receivers");
+    RecvAnyFuture::new(receivers)
+}
+
+/// Waits on receive and send operations of several channels and runs the body of the first one that completes,
+///  operations that are not selected have no effect on their channels.
+///
+/// Operations are written as `recv(receiver) -> result => body` and `send(sender, object) -> result => body`,
+///  where result is a pattern for `Result<T, ReceiveError>` and `Result<(), SendError<T>>`.
+///  An optional `default => body` operation runs if no operation can complete without waiting,
+///  and an optional `timeout(duration) => body` operation runs if no operation completes in time.
+///  Bodies run in the scope of the caller, so they can `break`, `continue` or `return`.
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let (s1, r1) = kanal::bounded::<u64>(1);
+/// let (s2, r2) = kanal::unbounded::<&str>();
+/// s2.send("hello")?;
+///
+/// kanal::select! {
+///     recv(r1) -> v => panic!("unexpected {:?}", v),
+///     recv(r2) -> v => assert_eq!(v?, "hello"),
+/// }
+/// kanal::select! {
+///     send(s1, 7) -> res => res?,
+///     recv(r2) -> _ => unreachable!(),
+///     timeout(Duration::from_millis(10)) => unreachable!(),
+/// }
+/// kanal::select! {
+///     recv(r2) -> _ => unreachable!(),
+///     default => assert_eq!(r1.recv()?, 7),
+/// }
+/// # anyhow::Ok(())
+/// ```
+#[macro_export]
+macro_rules! select {
+    ($($tokens:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut __select = $crate::Select::new();
+        $crate::__select_internal!(@munch __select [] [] $($tokens)*)
+    }};
+}
+
+/// Internal rules of [`select!`](crate::select), operations are registered one by one in nested scopes,
+///  then the completed operation is dispatched to its body.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __select_internal {
+    (@munch $sel:ident [$($arms:tt)*] [$($fb:tt)*]
+        recv($r:expr) -> $p:pat => $body:block $(,)? $($rest:tt)*) => {{
+        let __handle = &$r;
+        let __index = $sel.recv(__handle);
+        $crate::__select_internal!(@munch $sel [$($arms)* (recv __handle __index ($p) $body)] [$($fb)*] $($rest)*)
+    }};
+    (@munch $sel:ident [$($arms:tt)*] [$($fb:tt)*]
+        recv($r:expr) -> $p:pat => $body:expr $(, $($rest:tt)*)?) => {{
+        let __handle = &$r;
+        let __index = $sel.recv(__handle);
+        $crate::__select_internal!(@munch $sel [$($arms)* (recv __handle __index ($p) { $body })] [$($fb)*] $($($rest)*)?)
+    }};
+    (@munch $sel:ident [$($arms:tt)*] [$($fb:tt)*]
+        send($s:expr, $v:expr) -> $p:pat => $body:block $(,)? $($rest:tt)*) => {{
+        let __handle = &$s;
+        let __index = $sel.send(__handle, $v);
+        $crate::__select_internal!(@munch $sel [$($arms)* (send __handle __index ($p) $body)] [$($fb)*] $($rest)*)
+    }};
+    (@munch $sel:ident [$($arms:tt)*] [$($fb:tt)*]
+        send($s:expr, $v:expr) -> $p:pat => $body:expr $(, $($rest:tt)*)?) => {{
+        let __handle = &$s;
+        let __index = $sel.send(__handle, $v);
+        $crate::__select_internal!(@munch $sel [$($arms)* (send __handle __index ($p) { $body })] [$($fb)*] $($($rest)*)?)
+    }};
+    (@munch $sel:ident [$($arms:tt)*] []
+        default => $body:block $(,)? $($rest:tt)*) => {
+        $crate::__select_internal!(@munch $sel [$($arms)*] [default $body] $($rest)*)
+    };
+    (@munch $sel:ident [$($arms:tt)*] []
+        default => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::__select_internal!(@munch $sel [$($arms)*] [default { $body }] $($($rest)*)?)
+    };
+    (@munch $sel:ident [$($arms:tt)*] []
+        timeout($d:expr) => $body:block $(,)? $($rest:tt)*) => {
+        $crate::__select_internal!(@munch $sel [$($arms)*] [timeout ($d) $body] $($rest)*)
+    };
+    (@munch $sel:ident [$($arms:tt)*] []
+        timeout($d:expr) => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::__select_internal!(@munch $sel [$($arms)*] [timeout ($d) { $body }] $($($rest)*)?)
+    };
+    (@munch $sel:ident [$($arms:tt)*] [$($fb:tt)+] default $($rest:tt)*) => {
+        compile_error!("select can have at most one default or timeout operation")
+    };
+    (@munch $sel:ident [$($arms:tt)*] [$($fb:tt)+] timeout $($rest:tt)*) => {
+        compile_error!("select can have at most one default or timeout operation")
+    };
+    (@munch $sel:ident [$($arms:tt)*] [$($fb:tt)*]) => {
+        $crate::__select_internal!(@run $sel [$($arms)*] $($fb)*)
+    };
+    (@munch $sel:ident [$($arms:tt)*] [$($fb:tt)*] $($rest:tt)+) => {
+        compile_error!("expected `recv(r) -> res => body`, `send(s, v) -> res => body`, `default => body` or `timeout(d) => body`")
+    };
+
+    (@run $sel:ident [$($arms:tt)*]) => {{
+        let __op = $sel.select();
+        $crate::__select_internal!(@dispatch __op $($arms)*)
+    }};
+    (@run $sel:ident [$($arms:tt)*] default $body:block) => {
+        match $sel.try_select() {
+            Some(__op) => $crate::__select_internal!(@dispatch __op $($arms)*),
+            None => $body,
+        }
+    };
+    (@run $sel:ident [$($arms:tt)*] timeout ($d:expr) $body:block) => {
+        match $sel.select_timeout($d) {
+            Some(__op) => $crate::__select_internal!(@dispatch __op $($arms)*),
+            None => $body,
+        }
+    };
+
+    (@dispatch $op:ident) => {
+        unreachable!("select completed an unknown operation")
+    };
+    (@dispatch $op:ident (recv $h:ident $i:ident ($p:pat) $body:block) $($rest:tt)*) => {
+        if $op.index() == $i {
+            let $p = $op.recv($h);
+            $body
+        } else {
+            $crate::__select_internal!(@dispatch $op $($rest)*)
+        }
+    };
+    (@dispatch $op:ident (send $h:ident $i:ident ($p:pat) $body:block) $($rest:tt)*) => {
+        if $op.index() == $i {
+            let $p = $op.send($h);
+            $body
+        } else {
+            $crate::__select_internal!(@dispatch $op $($rest)*)
+        }
+    };
+}
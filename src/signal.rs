@@ -2,16 +2,24 @@ use crate::backoff;
 use crate::pointer::KanalPtr;
 use crate::state::{State, LOCKED, TERMINATED, UNLOCKED};
 use crate::sync::{SysWait, WaitAPI};
+use crate::waitlist::WaitNode;
+use std::cell::Cell;
 use std::sync::atomic::{fence, Ordering};
 #[cfg(feature = "async")]
 use std::task::{Poll, Waker};
 use std::time::Instant;
 
+/// Default count of spins of sync signals before they wait on the os
+pub(crate) const SPIN_COUNT: usize = 256;
+/// Count of spins of sync signals with a deadline before they wait on the os
+const TIMEOUT_SPIN_COUNT: usize = 32;
+
 #[cfg(feature = "async")]
 pub struct AsyncSignal<T> {
     state: State,
     ptr: KanalPtr<T>,
     waker: Option<Waker>,
+    node: WaitNode<T>,
 }
 
 #[cfg(feature = "async")]
@@ -25,6 +33,15 @@ impl<T> std::future::Future for AsyncSignal<T> {
         self: std::pin::Pin<&mut Self>,
         _cx: &mut std::task::Context<'_>,
     ) -> Poll<Self::Output> {
+        self.poll_finished()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> AsyncSignal<T> {
+    /// Returns the result of the signal if a peer finished it, it doesn't need the signal to be pinned as it only reads the state
+    #[inline(always)]
+    pub fn poll_finished(&self) -> Poll<u8> {
         let v = self.state.relaxed();
         if v >= LOCKED {
             Poll::Pending
@@ -33,10 +50,7 @@ impl<T> std::future::Future for AsyncSignal<T> {
             Poll::Ready(v)
         }
     }
-}
 
-#[cfg(feature = "async")]
-impl<T> AsyncSignal<T> {
     /// Signal to send data to a writer
     #[inline(always)]
     pub fn new() -> Self {
@@ -44,6 +58,7 @@ impl<T> AsyncSignal<T> {
             state: State::locked(),
             ptr: Default::default(),
             waker: Default::default(),
+            node: WaitNode::new(),
         }
     }
     /// Signal to send data to a writer for specific kanal pointer
@@ -53,6 +68,7 @@ impl<T> AsyncSignal<T> {
             state: State::locked(),
             ptr,
             waker: Default::default(),
+            node: WaitNode::new(),
         }
     }
     /// Set pointer to data for receiving or sending
@@ -84,21 +100,47 @@ impl<T> AsyncSignal<T> {
     /// Safety: it's only safe to call on receive signals that are not terminated
     #[inline(always)]
     pub unsafe fn send(this: *const Self, d: T) {
+        AsyncSignal::complete_send(this, d).wake();
+    }
+
+    /// Receives object from signal
+    /// Safety: it's only safe to call on send signals that are not terminated
+    #[inline(always)]
+    pub unsafe fn recv(this: *const Self) -> T {
+        let (r, waker) = AsyncSignal::complete_recv(this);
+        waker.wake();
+        r
+    }
+
+    /// Sends object to the signal without waking its task, and returns the waker of the task.
+    /// The signal is finished on return, so the owner may release it right after that.
+    /// Safety: it's only safe to call on receive signals that are not terminated
+    #[inline(always)]
+    pub unsafe fn complete_send(this: *const Self, d: T) -> Waker {
         (*this).ptr.write(d);
         let waker = AsyncSignal::clone_waker(this);
         (*this).state.force_unlock();
-        waker.wake();
+        waker
     }
 
-    /// Receives object from signal
+    /// Receives object from the signal without waking its task, and returns the object with the waker of the task.
+    /// The signal is finished on return, so the owner may release it right after that.
     /// Safety: it's only safe to call on send signals that are not terminated
     #[inline(always)]
-    pub unsafe fn recv(this: *const Self) -> T {
+    pub unsafe fn complete_recv(this: *const Self) -> (T, Waker) {
         let waker = AsyncSignal::clone_waker(this);
         let r = (*this).ptr.read();
         (*this).state.force_unlock();
-        waker.wake();
-        r
+        (r, waker)
+    }
+
+    /// Returns result of a signal that is already finished by a peer, peers finish async signals
+    ///  of channels under the lock, so the result is ready once the signal is not in the waitlist anymore
+    #[inline(always)]
+    pub fn result(&self) -> u8 {
+        let v = self.state.acquire();
+        debug_assert!(v < LOCKED, "signal is not finished");
+        v
     }
 
     /// Terminates operation and notifies the waiter , shall not be called more than once
@@ -117,9 +159,14 @@ impl<T> AsyncSignal<T> {
         let _ = (*this).ptr.read();
     }
 
-    /// Waits for signal and returns true if send/recv operation was successful
-    pub fn wait_indefinitely(&self) -> u8 {
-        self.state.wait_indefinitely()
+    /// Wakes the task of a select signal without sending data, unlike `send` it can be called several times
+    ///  as it doesn't change the state, so the signal can be shared among waitlists of several channels
+    /// Safety: it's only safe to call while the owner can't release the signal or change its waker, e.g. under the lock of the waitlist
+    #[inline(always)]
+    pub unsafe fn notify(this: *const Self) {
+        if let Some(waker) = &(*this).waker {
+            waker.wake_by_ref();
+        }
     }
 
     /// Register waker for async
@@ -149,6 +196,7 @@ pub struct SyncSignal<T> {
     state: State,
     ptr: KanalPtr<T>,
     os_signal: SysWait,
+    node: WaitNode<T>,
 }
 
 unsafe impl<T> Send for SyncSignal<T> {}
@@ -163,6 +211,7 @@ impl<T> SyncSignal<T> {
             state: State::locked(),
             ptr,
             os_signal: SysWait::new(),
+            node: WaitNode::new(),
         }
     }
 
@@ -198,11 +247,18 @@ impl<T> SyncSignal<T> {
     #[inline(always)]
     pub unsafe fn recv(this: *const Self) -> T {
         let d = (*this).ptr.read();
+        SyncSignal::release(this);
+        d
+    }
+
+    /// Unlocks the send signal after its object is read and wakes the waiter
+    /// Safety: it's only safe to call once on send signals that their object is read with `assume_init`
+    #[inline(always)]
+    pub unsafe fn release(this: *const Self) {
         if !(*this).state.unlock() {
             (*this).state.force_unlock();
             (*this).os_signal.wake();
         }
-        d
     }
 
     /// Assumes data inside self.ptr is correct and reads it.
@@ -230,16 +286,33 @@ impl<T> SyncSignal<T> {
         }
     }
 
+    /// Unlocks the signal without sending data and wakes the waiter, unlike `send` it can be called several times
+    ///  and only the first call has an effect, so the signal can be shared among waitlists of several channels
+    /// Safety: it's only safe to call while the owner of the signal can't release it, e.g. under the lock of the waitlist
+    #[inline(always)]
+    pub unsafe fn notify(this: *const Self) {
+        if !(*this).state.unlock() && (*this).state.unlock_starvation() {
+            (*this).os_signal.wake();
+        }
+    }
+
     /// Waits for signal and returns true if send/recv operation was successful
     #[inline(always)]
     pub fn wait(&self) -> bool {
+        self.wait_spin(SPIN_COUNT)
+    }
+
+    /// Waits for signal with the provided count of spins before waiting on the os,
+    ///  and returns true if send/recv operation was successful
+    #[inline(always)]
+    pub fn wait_spin(&self, spins: usize) -> bool {
         let v = self.state.relaxed();
         if v < LOCKED {
             fence(Ordering::Acquire);
             return v == UNLOCKED;
         }
 
-        for _ in 0..256 {
+        for _ in 0..spins {
             //backoff::spin_wait(96);
             backoff::yield_now_std();
             let v = self.state.relaxed();
@@ -255,12 +328,35 @@ impl<T> SyncSignal<T> {
         self.state.acquire() == UNLOCKED
     }
 
-    /// Waits for signal and returns true if send/recv operation was successful
+    /// Waits for signal until the deadline and returns true if send/recv operation was successful,
+    ///  it spins for a short time and then sleeps on the os until the deadline
     #[inline(always)]
     pub fn wait_timeout(&self, until: Instant) -> bool {
-        let v = self.state.wait_unlock_until(until);
-        fence(Ordering::Acquire);
-        v == UNLOCKED
+        let v = self.state.relaxed();
+        if v < LOCKED {
+            fence(Ordering::Acquire);
+            return v == UNLOCKED;
+        }
+
+        for _ in 0..TIMEOUT_SPIN_COUNT {
+            // randomize next entry with yield_now
+            backoff::yield_now();
+            let v = self.state.relaxed();
+            if v < LOCKED {
+                fence(Ordering::Acquire);
+                return v == UNLOCKED;
+            }
+        }
+
+        if self.state.upgrade_lock()
+            && !self.os_signal.wait_until(until)
+            && !self.state.downgrade_lock()
+        {
+            // the peer finished the operation after the deadline and it's going to wake the waiter,
+            //  the signal can't be released before the wake up
+            self.os_signal.wait();
+        }
+        self.state.acquire() == UNLOCKED
     }
 
     /// Returns whether the signal is terminated by the `terminate` function or not
@@ -270,18 +366,76 @@ impl<T> SyncSignal<T> {
     }
 }
 
-/// Signal enum encapsulates both SyncSignal and AsyncSignal to enable them to operate in the same context
+/// Shared signal of a select operation, it's notified by the peers instead of moving data to it
 #[derive(Clone, Copy, Debug)]
+pub enum SelectSignal {
+    Sync(*const SyncSignal<()>),
+    #[cfg(feature = "async")]
+    Async(*const AsyncSignal<()>),
+}
+
+/// Registration of a select signal in the waitlist of a channel, a select signal is registered in the waitlists
+///  of several channels at once, so each registration links its own node
+pub struct SelectEntry<T> {
+    node: WaitNode<T>,
+    sig: Cell<SelectSignal>,
+}
+
+// Safety: the entry is only accessed by its owner and under the lock of the channel that links it
+unsafe impl<T> Send for SelectEntry<T> {}
+
+impl<T> SelectEntry<T> {
+    pub fn new() -> Self {
+        Self {
+            node: WaitNode::new(),
+            sig: Cell::new(SelectSignal::Sync(std::ptr::null())),
+        }
+    }
+
+    /// Binds the entry to the select signal and converts it to common signal that works with channel internal
+    /// Safety: it's only safe to bind entries that are not linked in a waitlist
+    #[inline(always)]
+    pub unsafe fn bind(&self, sig: SelectSignal) -> Signal<T> {
+        self.sig.set(sig);
+        self.as_signal()
+    }
+
+    /// Convert select entry to common signal that works with channel internal
+    #[inline(always)]
+    pub fn as_signal(&self) -> Signal<T> {
+        Signal::Select(self as *const Self)
+    }
+}
+
+impl<T> Default for SelectEntry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Signal enum encapsulates both SyncSignal and AsyncSignal to enable them to operate in the same context
+#[derive(Debug)]
 pub enum Signal<T> {
     Sync(*const SyncSignal<T>),
     #[cfg(feature = "async")]
     Async(*const AsyncSignal<T>),
+    /// Notification only signal of a select operation, its select signal is shared among waitlists of several channels,
+    ///  peers notify it and continue with the next signal of the waitlist instead of moving data to it
+    Select(*const SelectEntry<T>),
 }
 // Safety: if T is Send/Sync, the Signal<T> is safe to move
 unsafe impl<T> Sync for Signal<T> {}
 // Safety: if T is Send/Sync, the Signal<T> is safe to move
 unsafe impl<T> Send for Signal<T> {}
 
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Signal<T> {}
+
 #[allow(dead_code)]
 impl<T> Signal<T> {
     /// Waits for the signal event in sync mode,
@@ -291,6 +445,11 @@ impl<T> Signal<T> {
             Signal::Sync(sig) => (**sig).wait(),
             #[cfg(feature = "async")]
             Signal::Async(_sig) => unreachable!("async sig: sync wait must not happen"),
+            Signal::Select(entry) => match (**entry).sig.get() {
+                SelectSignal::Sync(sig) => (*sig).wait(),
+                #[cfg(feature = "async")]
+                SelectSignal::Async(_sig) => unreachable!("async sig: sync wait must not happen"),
+            },
         }
     }
 
@@ -301,6 +460,44 @@ impl<T> Signal<T> {
             Signal::Sync(sig) => SyncSignal::send(*sig, d),
             #[cfg(feature = "async")]
             Signal::Async(sig) => AsyncSignal::send(*sig, d),
+            Signal::Select(_entry) => {
+                unreachable!("select sig: data must not be sent to select signals")
+            }
+        }
+    }
+
+    /// Sends object to receive signal that is taken from a waitlist under the lock of its channel,
+    ///  the rest of the operation is returned to be finished after the lock is released.
+    /// Async signals are finished right away, so the owner of a future never waits for a peer to cancel its operation,
+    ///  while sync signals are finished by the handoff to keep os wake ups out of the lock
+    /// Safety: it's only safe to be called only once on the receive signals that are not terminated, under the lock of the waitlist
+    #[inline(always)]
+    pub unsafe fn send_locked(&self, d: T) -> Handoff<T> {
+        match self {
+            Signal::Sync(sig) => Handoff::Send(*sig, d),
+            #[cfg(feature = "async")]
+            Signal::Async(sig) => Handoff::Wake(AsyncSignal::complete_send(*sig, d)),
+            Signal::Select(_entry) => {
+                unreachable!("select sig: data must not be sent to select signals")
+            }
+        }
+    }
+
+    /// Receives object from send signal that is taken from a waitlist under the lock of its channel,
+    ///  the rest of the operation is returned to be finished after the lock is released, see `send_locked`
+    /// Safety: it's only safe to be called only once on send signals that are not terminated, under the lock of the waitlist
+    #[inline(always)]
+    pub unsafe fn recv_locked(&self) -> (T, Handoff<T>) {
+        match self {
+            Signal::Sync(sig) => ((**sig).assume_init(), Handoff::Release(*sig)),
+            #[cfg(feature = "async")]
+            Signal::Async(sig) => {
+                let (d, waker) = AsyncSignal::complete_recv(*sig);
+                (d, Handoff::Wake(waker))
+            }
+            Signal::Select(_entry) => {
+                unreachable!("select sig: data must not be received from select signals")
+            }
         }
     }
 
@@ -311,6 +508,9 @@ impl<T> Signal<T> {
             Signal::Sync(sig) => SyncSignal::recv(*sig),
             #[cfg(feature = "async")]
             Signal::Async(sig) => AsyncSignal::recv(*sig),
+            Signal::Select(_entry) => {
+                unreachable!("select sig: data must not be received from select signals")
+            }
         }
     }
 
@@ -321,6 +521,8 @@ impl<T> Signal<T> {
             Signal::Sync(sig) => SyncSignal::terminate(*sig),
             #[cfg(feature = "async")]
             Signal::Async(sig) => AsyncSignal::terminate(*sig),
+            // the select operation checks the state of its channels again after the notification
+            Signal::Select(_) => self.notify(),
         }
     }
 
@@ -331,6 +533,66 @@ impl<T> Signal<T> {
             Signal::Sync(sig) => SyncSignal::load_and_drop(*sig),
             #[cfg(feature = "async")]
             Signal::Async(sig) => AsyncSignal::load_and_drop(*sig),
+            Signal::Select(_entry) => {}
+        }
+    }
+
+    /// Returns whether the signal is a notification only signal of a select operation
+    #[inline(always)]
+    pub fn is_select(&self) -> bool {
+        matches!(self, Signal::Select(_))
+    }
+
+    /// Notifies the waiter of a select signal, it can be called several times
+    /// Safety: it's only safe to call on select signals while they are owned by the waitlist
+    #[inline(always)]
+    pub unsafe fn notify(&self) {
+        match self {
+            Signal::Select(entry) => match (**entry).sig.get() {
+                SelectSignal::Sync(sig) => SyncSignal::notify(sig),
+                #[cfg(feature = "async")]
+                SelectSignal::Async(sig) => AsyncSignal::notify(sig),
+            },
+            _ => unreachable!("only select signals can be notified"),
+        }
+    }
+
+    /// Returns the waitlist node of the signal
+    /// Safety: it's only safe to call on signals that are alive
+    #[inline(always)]
+    pub unsafe fn node(&self) -> *const WaitNode<T> {
+        match self {
+            Signal::Sync(sig) => &(**sig).node,
+            #[cfg(feature = "async")]
+            Signal::Async(sig) => &(**sig).node,
+            Signal::Select(entry) => &(**entry).node,
+        }
+    }
+}
+
+/// Rest of an operation on a signal that is taken from a waitlist, it's finished after the lock of the channel is released
+#[must_use = "the waiter of the signal is not woken until the handoff is finished"]
+pub enum Handoff<T> {
+    /// The object is not sent to the sync signal yet
+    Send(*const SyncSignal<T>, T),
+    /// The object of the sync signal is read, and the signal is not unlocked yet
+    Release(*const SyncSignal<T>),
+    /// The async signal is finished, and its task is not woken yet
+    #[cfg(feature = "async")]
+    Wake(Waker),
+}
+
+impl<T> Handoff<T> {
+    /// Finishes the operation and wakes the waiter of the signal
+    #[inline(always)]
+    pub fn finish(self) {
+        match self {
+            // Safety: sync waiters can't release their signal before it's unlocked
+            Handoff::Send(sig, d) => unsafe { SyncSignal::send(sig, d) },
+            // Safety: sync waiters can't release their signal before it's unlocked
+            Handoff::Release(sig) => unsafe { SyncSignal::release(sig) },
+            #[cfg(feature = "async")]
+            Handoff::Wake(waker) => waker.wake(),
         }
     }
 }
@@ -338,7 +600,7 @@ impl<T> Signal<T> {
 impl<T> Default for Signal<T> {
     fn default() -> Self {
         // Safety: it's not safe to use this signal, it's only a place holder.
-        Signal::Sync(std::ptr::null() as *const SyncSignal<T>)
+        Signal::Sync(std::ptr::null())
     }
 }
 
@@ -349,7 +611,7 @@ impl<T> PartialEq for Signal<T> {
             (Self::Sync(l0), Self::Sync(r0)) => l0 == r0,
             #[cfg(feature = "async")]
             (Self::Async(l0), Self::Async(r0)) => l0 == r0,
-            #[cfg(feature = "async")]
+            (Self::Select(l0), Self::Select(r0)) => l0 == r0,
             _ => false,
         }
     }
@@ -1,11 +1,4 @@
-#[cfg(feature = "async")]
-use std::time::Duration;
-use std::{
-    sync::atomic::{fence, AtomicU8, Ordering},
-    time::Instant,
-};
-
-use crate::backoff;
+use std::sync::atomic::{AtomicU8, Ordering};
 
 /// The state keeps the state of signals in both sync and async to make eventing for senders and receivers possible
 pub struct State {
@@ -37,67 +30,6 @@ impl State {
         self.v.load(Ordering::Acquire)
     }
 
-    /// Waits synchronously without putting the thread to sleep until the instant time is reached
-    /// this function may return with latency after instant time because of spin loop implementation
-    #[inline(always)]
-    #[must_use = "ignoring wait functions return value will lead to UB"]
-    pub fn wait_unlock_until(&self, until: Instant) -> u8 {
-        for _ in 0..32 {
-            let v = self.v.load(Ordering::Relaxed);
-            if v < LOCKED {
-                fence(Ordering::Acquire);
-                return v;
-            }
-            // randomize next entry with yield_now
-            backoff::yield_now();
-        }
-        //return self.v.load(Ordering::Acquire);
-        while Instant::now() < until {
-            let v = self.v.load(Ordering::Relaxed);
-            if v < LOCKED {
-                fence(Ordering::Acquire);
-                return v;
-            }
-            backoff::yield_now_std();
-        }
-        self.v.load(Ordering::Acquire)
-    }
-
-    /// Waits synchronously for the signal in sync mode, it should not be used anywhere except a drop of async future
-    #[cfg(feature = "async")]
-    #[must_use = "ignoring wait functions return value will lead to UB"]
-    pub fn wait_indefinitely(&self) -> u8 {
-        let v = self.relaxed();
-        if v < LOCKED {
-            fence(Ordering::Acquire);
-            return v;
-        }
-
-        for _ in 0..32 {
-            //backoff::spin_wait(96);
-            backoff::yield_now_std();
-            let v = self.relaxed();
-            if v < LOCKED {
-                fence(Ordering::Acquire);
-                return v;
-            }
-        }
-
-        let mut sleep_time: u64 = 1 << 10;
-        loop {
-            backoff::sleep(Duration::from_nanos(sleep_time));
-            let v = self.relaxed();
-            if v < LOCKED {
-                fence(Ordering::Acquire);
-                return v;
-            }
-            // increase sleep_time gradually to 262 microseconds
-            if sleep_time < (1 << 18) {
-                sleep_time <<= 1;
-            }
-        }
-    }
-
     /// Unlocks the state and changes it to a successful state
     #[inline(always)]
     pub unsafe fn unlock(&self) -> bool {
@@ -126,6 +58,19 @@ impl State {
         self.v.store(TERMINATED, Ordering::Release)
     }
 
+    /// Unlocks the state if the waiter upgraded the lock to starvation mode, returns true if the waiter needs a wake up
+    #[inline(always)]
+    pub unsafe fn unlock_starvation(&self) -> bool {
+        self.v
+            .compare_exchange(
+                LOCKED_STARVATION,
+                UNLOCKED,
+                Ordering::Release,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+
     /// Tries to upgrade the lock to starvation mode
     #[inline(always)]
     pub fn upgrade_lock(&self) -> bool {
@@ -138,4 +83,18 @@ impl State {
             )
             .is_ok()
     }
+
+    /// Tries to downgrade the lock from starvation mode, it fails if the peer already unlocked the state
+    ///  and is going to wake the waiter
+    #[inline(always)]
+    pub fn downgrade_lock(&self) -> bool {
+        self.v
+            .compare_exchange(
+                LOCKED_STARVATION,
+                LOCKED,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
 }
@@ -8,16 +8,45 @@
 //   but further investigation into the availability and performance of these APIs is needed.
 pub use imp::*;
 
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "openbsd",
+    target_os = "freebsd"
+))]
+use std::time::Duration;
+use std::time::Instant;
+
 pub trait WaitAPI {
     fn wait(&self);
+    /// Waits until the waiter is woken up or the deadline is reached, returns true if it's woken up.
+    /// After a timeout the wake up may still arrive, so the caller should consume it with `wait`
+    ///  if it can't make sure that no waker is going to wake it.
+    fn wait_until(&self, until: Instant) -> bool;
     fn wake(&self);
     fn new() -> Self;
 }
 
+/// Returns the relative timeout of the duration for the os, saturated to the maximum seconds of the platform
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "openbsd",
+    target_os = "freebsd"
+))]
+#[inline(always)]
+fn timespec(dur: Duration) -> libc::timespec {
+    // Safety: timespec is a plain struct, zero is a valid value for all of its fields
+    let mut ts: libc::timespec = unsafe { std::mem::zeroed() };
+    ts.tv_sec = dur.as_secs().min(libc::time_t::MAX as u64) as libc::time_t;
+    ts.tv_nsec = dur.subsec_nanos() as _;
+    ts
+}
+
 #[cfg(any(target_os = "linux", target_os = "android"))]
 mod imp {
-    use super::WaitAPI;
-    use std::sync::atomic::AtomicU32;
+    use super::{timespec, WaitAPI};
+    use std::{sync::atomic::AtomicU32, time::Instant};
     //       long syscall(SYS_futex, uint32_t *uaddr, int futex_op, uint32_t val,
     //                const struct timespec *timeout,   /* or: uint32_t val2 */
     //                uint32_t *uaddr2, uint32_t val3);
@@ -34,6 +63,20 @@ mod imp {
             ) != -1
         }
     }
+    // returns false on timeout or interrupt
+    #[inline(always)]
+    fn futex_wait_timeout(futex: &AtomicU32, expected: i32, timeout: &libc::timespec) -> bool {
+        unsafe {
+            // Safety: futex pointer address and timeout are always valid.
+            libc::syscall(
+                libc::SYS_futex,
+                futex as *const AtomicU32 as *mut u32,
+                libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG,
+                expected,
+                timeout as *const libc::timespec,
+            ) != -1
+        }
+    }
     // returns false if action fails
     #[inline(always)]
     fn futex_wake(futex: &AtomicU32) -> bool {
@@ -56,6 +99,20 @@ mod imp {
             futex_wait(&self.futex, 0);
         }
 
+        #[inline(always)]
+        fn wait_until(&self, until: Instant) -> bool {
+            loop {
+                let now = Instant::now();
+                if now >= until {
+                    return false;
+                }
+                // FUTEX_WAIT takes a relative timeout, and it's recalculated after interrupts
+                if futex_wait_timeout(&self.futex, 0, &timespec(until - now)) {
+                    return true;
+                }
+            }
+        }
+
         #[inline(always)]
         fn wake(&self) {
             while !futex_wake(&self.futex) {}
@@ -70,8 +127,8 @@ mod imp {
 
 #[cfg(target_os = "openbsd")]
 mod imp {
-    use super::WaitAPI;
-    use std::sync::atomic::AtomicU32;
+    use super::{timespec, WaitAPI};
+    use std::{sync::atomic::AtomicU32, time::Instant};
     #[inline(always)]
     fn futex_wait(futex: &AtomicU32, expected: i32) {
         unsafe {
@@ -85,6 +142,20 @@ mod imp {
             );
         }
     }
+    // returns false on timeout or interrupt
+    #[inline(always)]
+    fn futex_wait_timeout(futex: &AtomicU32, expected: i32, timeout: &libc::timespec) -> bool {
+        unsafe {
+            // Safety: futex pointer address and timeout are always valid.
+            libc::futex(
+                futex as *const AtomicU32 as *mut _,
+                libc::FUTEX_WAIT,
+                expected,
+                timeout as *const libc::timespec,
+                std::ptr::null_mut(),
+            ) == 0
+        }
+    }
     // returns false if action fails
     #[inline(always)]
     fn futex_wake(futex: &AtomicU32) -> bool {
@@ -110,6 +181,19 @@ mod imp {
             futex_wait(&self.futex, 0i32);
         }
 
+        #[inline(always)]
+        fn wait_until(&self, until: Instant) -> bool {
+            loop {
+                let now = Instant::now();
+                if now >= until {
+                    return false;
+                }
+                if futex_wait_timeout(&self.futex, 0i32, &timespec(until - now)) {
+                    return true;
+                }
+            }
+        }
+
         #[inline(always)]
         fn wake(&self) {
             while !futex_wake(&self.futex) {}
@@ -125,7 +209,7 @@ mod imp {
 #[cfg(target_os = "emscripten")]
 mod imp {
     use super::WaitAPI;
-    use std::sync::atomic::AtomicU32;
+    use std::{sync::atomic::AtomicU32, time::Instant};
 
     extern "C" {
         fn emscripten_futex_wake(addr: *const AtomicU32, count: libc::c_int) -> libc::c_int;
@@ -143,6 +227,12 @@ mod imp {
             emscripten_futex_wait(futex as *const AtomicU32, expected, f64::INFINITY);
         }
     }
+    // returns false on timeout
+    #[inline(always)]
+    fn futex_wait_ms(futex: &AtomicU32, expected: libc::c_uint, max_wait_ms: f64) -> bool {
+        // Safety: futex pointer address is always valid.
+        unsafe { emscripten_futex_wait(futex as *const AtomicU32, expected, max_wait_ms) == 0 }
+    }
     // returns false if action fails
     #[inline(always)]
     fn futex_wake(futex: &AtomicU32) -> bool {
@@ -160,6 +250,20 @@ mod imp {
             futex_wait(&self.futex, 0i32);
         }
 
+        #[inline(always)]
+        fn wait_until(&self, until: Instant) -> bool {
+            loop {
+                let now = Instant::now();
+                if now >= until {
+                    return false;
+                }
+                let max_wait_ms = (until - now).as_secs_f64() * 1000.0;
+                if futex_wait_ms(&self.futex, 0, max_wait_ms) {
+                    return true;
+                }
+            }
+        }
+
         #[inline(always)]
         fn wake(&self) {
             while !futex_wake(&self.futex) {}
@@ -174,8 +278,12 @@ mod imp {
 
 #[cfg(target_os = "freebsd")]
 mod imp {
-    use super::WaitAPI;
-    use std::sync::atomic::{AtomicU32, Ordering};
+    use super::{timespec, WaitAPI};
+    use std::{
+        mem::size_of,
+        sync::atomic::{AtomicU32, Ordering},
+        time::Instant,
+    };
 
     pub fn futex_wake(futex: &AtomicU32) {
         futex.store(!0, Ordering::Relaxed);
@@ -205,6 +313,29 @@ mod imp {
             futex.load(Ordering::Relaxed) == 0
         } {}
     }
+    pub fn futex_wait_until(futex: &AtomicU32, expected: libc::c_ulong, until: Instant) -> bool {
+        loop {
+            if futex.load(Ordering::Relaxed) != 0 {
+                return true;
+            }
+            let now = Instant::now();
+            if now >= until {
+                return false;
+            }
+            let mut timeout = timespec(until - now);
+            // Safety: futex pointer address and timeout are always valid, the size of the timeout
+            //  is passed in place of the first address as the api requires.
+            unsafe {
+                libc::_umtx_op(
+                    futex as *const AtomicU32 as *mut _,
+                    libc::UMTX_OP_WAIT_UINT_PRIVATE,
+                    expected,
+                    size_of::<libc::timespec>() as *mut _,
+                    &mut timeout as *mut libc::timespec as *mut _,
+                );
+            }
+        }
+    }
 
     pub struct SysWait {
         futex: AtomicU32,
@@ -216,6 +347,11 @@ mod imp {
             futex_wait(&self.futex, 0);
         }
 
+        #[inline(always)]
+        fn wait_until(&self, until: Instant) -> bool {
+            futex_wait_until(&self.futex, 0, until)
+        }
+
         #[inline(always)]
         fn wake(&self) {
             futex_wake(&self.futex);
@@ -231,7 +367,10 @@ mod imp {
 #[cfg(target_os = "dragonfly")]
 mod imp {
     use super::WaitAPI;
-    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::{
+        sync::atomic::{AtomicU32, Ordering},
+        time::Instant,
+    };
 
     pub fn futex_wake(futex: &AtomicU32) {
         unsafe {
@@ -251,6 +390,24 @@ mod imp {
             futex.load(Ordering::Relaxed) == 0
         } {}
     }
+    pub fn futex_wait_until(futex: &AtomicU32, expected: libc::c_int, until: Instant) -> bool {
+        loop {
+            if futex.load(Ordering::Relaxed) != 0 {
+                return true;
+            }
+            let now = Instant::now();
+            if now >= until {
+                return false;
+            }
+            // the timeout is in microseconds and zero means no timeout, so it's kept in 1..=c_int::MAX
+            let micros =
+                (until - now).as_micros().clamp(1, libc::c_int::MAX as u128) as libc::c_int;
+            // Safety: futex pointer address is always valid.
+            unsafe {
+                libc::umtx_sleep(futex as *const AtomicU32 as *const _, expected, micros);
+            }
+        }
+    }
 
     pub struct SysWait {
         futex: AtomicU32,
@@ -262,6 +419,11 @@ mod imp {
             futex_wait(&self.futex, 0);
         }
 
+        #[inline(always)]
+        fn wait_until(&self, until: Instant) -> bool {
+            futex_wait_until(&self.futex, 0, until)
+        }
+
         #[inline(always)]
         fn wake(&self) {
             futex_wake(&self.futex);
@@ -286,6 +448,7 @@ mod imp {
     use std::{
         sync::atomic::{AtomicBool, Ordering},
         thread::Thread,
+        time::Instant,
     };
 
     use super::WaitAPI;
@@ -312,6 +475,20 @@ mod imp {
             } {}
         }
 
+        #[inline(always)]
+        fn wait_until(&self, until: Instant) -> bool {
+            loop {
+                if !self.waiting.load(Ordering::Relaxed) {
+                    return true;
+                }
+                let now = Instant::now();
+                if now >= until {
+                    return false;
+                }
+                std::thread::park_timeout(until - now);
+            }
+        }
+
         #[inline(always)]
         fn wake(&self) {
             self.waiting.store(false, Ordering::Relaxed);
@@ -0,0 +1,234 @@
+/// Runtime agnostic timer for deadline aware async operations.
+/// Kanal futures should work with any executor, so instead of depending on the timer of a specific runtime,
+///  a single background thread keeps a heap of deadlines and wakes the registered wakers when they are reached.
+/// Canceled delays take their waker out of the shared slot, the empty entries are discarded when they reach
+///  the top of the heap, or all at once when they outnumber the live entries.
+/// If the timer thread can't be spawned, pending delays report their deadline as reached instead of hanging.
+/// The thread is spawned on the first registration, and it exits once the heap stays empty for `IDLE_TIMEOUT`,
+///  so programs that use timeouts only now and then don't keep a thread for the whole process lifetime.
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    sync::{Arc, Condvar, Mutex, OnceLock},
+    task::{Context, Waker},
+    time::{Duration, Instant},
+};
+
+/// Time that the timer thread waits for a new deadline before it exits
+const IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Waker holder that is shared between a delay and the timer thread
+struct Slot {
+    waker: Mutex<Option<Waker>>,
+}
+
+struct Entry {
+    deadline: Instant,
+    slot: Arc<Slot>,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed to turn the max heap into a min heap, nearest deadline comes first
+        other.deadline.cmp(&self.deadline)
+    }
+}
+
+struct TimerState {
+    heap: BinaryHeap<Entry>,
+    /// Number of entries in the heap that belong to canceled delays
+    canceled: usize,
+    /// Whether the timer thread is alive
+    running: bool,
+}
+
+struct Timer {
+    state: Mutex<TimerState>,
+    cond: Condvar,
+}
+
+impl Timer {
+    /// Registers the slot to be woken on the deadline, returns false if the timer thread is not available
+    fn register(&self, deadline: Instant, slot: Arc<Slot>) -> bool {
+        let mut state = self.state.lock().unwrap();
+        // only wake the timer thread if the new deadline is nearer than what it is sleeping for
+        let notify = match state.heap.peek() {
+            Some(e) => deadline < e.deadline,
+            None => true,
+        };
+        state.heap.push(Entry { deadline, slot });
+        if !state.running {
+            state.running = true;
+            drop(state);
+            if std::thread::Builder::new()
+                .name("kanal-timer".into())
+                .spawn(|| timer().run())
+                .is_err()
+            {
+                self.abandon();
+                return false;
+            }
+            return true;
+        }
+        drop(state);
+        if notify {
+            self.cond.notify_one();
+        }
+        true
+    }
+
+    /// Discards every entry after a failed spawn, so the next registration retries it.
+    /// Delays registered in the meantime are woken and find their slot empty, which they report as expired.
+    fn abandon(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.running = false;
+        state.canceled = 0;
+        let wakers: Vec<Waker> = state
+            .heap
+            .drain()
+            .filter_map(|entry| entry.slot.waker.lock().unwrap().take())
+            .collect();
+        drop(state);
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Cancels the entry of the slot, compacts the heap once canceled entries outnumber the live ones
+    fn cancel(&self, slot: &Slot) {
+        let mut state = self.state.lock().unwrap();
+        if slot.waker.lock().unwrap().take().is_none() {
+            // already expired or discarded, there is no entry left in the heap
+            return;
+        }
+        state.canceled += 1;
+        if state.canceled * 2 > state.heap.len() {
+            state
+                .heap
+                .retain(|entry| entry.slot.waker.lock().unwrap().is_some());
+            state.canceled = 0;
+        }
+    }
+
+    fn run(&self) {
+        let mut expired = Vec::new();
+        let mut state = self.state.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            while let Some(entry) = state.heap.peek() {
+                // canceled entries are discarded early to let the thread exit sooner
+                if entry.deadline > now && entry.slot.waker.lock().unwrap().is_some() {
+                    break;
+                }
+                let entry = state.heap.pop().unwrap();
+                let waker = entry.slot.waker.lock().unwrap().take();
+                match waker {
+                    Some(waker) => expired.push(waker),
+                    None => state.canceled -= 1,
+                }
+            }
+            if !expired.is_empty() {
+                // wake tasks outside of the heap lock
+                drop(state);
+                for waker in expired.drain(..) {
+                    waker.wake();
+                }
+                state = self.state.lock().unwrap();
+                continue;
+            }
+            state = match state.heap.peek() {
+                Some(entry) => {
+                    let timeout = entry.deadline.saturating_duration_since(now);
+                    self.cond.wait_timeout(state, timeout).unwrap().0
+                }
+                None => {
+                    let (mut state, res) = self.cond.wait_timeout(state, IDLE_TIMEOUT).unwrap();
+                    if res.timed_out() && state.heap.is_empty() {
+                        // the next registration spawns the thread again
+                        state.running = false;
+                        return;
+                    }
+                    state
+                }
+            };
+        }
+    }
+}
+
+fn timer() -> &'static Timer {
+    static TIMER: OnceLock<Timer> = OnceLock::new();
+    TIMER.get_or_init(|| Timer {
+        state: Mutex::new(TimerState {
+            heap: BinaryHeap::new(),
+            canceled: 0,
+            running: false,
+        }),
+        cond: Condvar::new(),
+    })
+}
+
+/// Delay until a deadline, registers the task waker in the timer thread on first pending poll
+pub(crate) struct Delay {
+    deadline: Instant,
+    slot: Option<Arc<Slot>>,
+}
+
+impl Delay {
+    #[inline(always)]
+    pub(crate) fn new(deadline: Instant) -> Self {
+        Self {
+            deadline,
+            slot: None,
+        }
+    }
+
+    /// Returns true if the deadline is reached, otherwise makes sure the task gets woken on the deadline
+    pub(crate) fn poll_expired(&mut self, cx: &mut Context<'_>) -> bool {
+        if Instant::now() >= self.deadline {
+            return true;
+        }
+        match &self.slot {
+            Some(slot) => {
+                let mut waker = slot.waker.lock().unwrap();
+                match waker.as_ref() {
+                    Some(w) if w.will_wake(cx.waker()) => {}
+                    Some(_) => *waker = Some(cx.waker().clone()),
+                    // the timer discarded the entry before the deadline as its thread failed to spawn
+                    None => return true,
+                }
+            }
+            None => {
+                let slot = Arc::new(Slot {
+                    waker: Mutex::new(Some(cx.waker().clone())),
+                });
+                self.slot = Some(slot.clone());
+                if !timer().register(self.deadline, slot) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+impl Drop for Delay {
+    fn drop(&mut self) {
+        if let Some(slot) = &self.slot {
+            timer().cancel(slot);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::{cell::Cell, marker::PhantomPinned, ptr};
+
+use crate::signal::Signal;
+
+/// Node of a waiter in an intrusive waitlist, it's embedded in the signal of the waiter so linking a waiter
+///  doesn't allocate, and a waiter is removed from the middle of the waitlist in constant time.
+/// Nodes are only accessed under the lock of the channel that owns the waitlist, and the owner of a node
+///  must not move or release it while it's linked.
+/// Nodes are `!Unpin`, so futures that embed a signal are only polled when they are pinned, and can't be moved while they wait.
+pub struct WaitNode<T> {
+    prev: Cell<*const WaitNode<T>>,
+    next: Cell<*const WaitNode<T>>,
+    /// Signal of the waiter, it's set when the node is linked
+    signal: Cell<Signal<T>>,
+    linked: Cell<bool>,
+    _pinned: PhantomPinned,
+}
+
+impl<T> WaitNode<T> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            prev: Cell::new(ptr::null()),
+            next: Cell::new(ptr::null()),
+            signal: Cell::new(Signal::default()),
+            linked: Cell::new(false),
+            _pinned: PhantomPinned,
+        }
+    }
+}
+
+impl<T> Default for WaitNode<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Intrusive doubly linked list of the signals of waiters, signals are linked through their embedded nodes
+pub struct WaitList<T> {
+    head: *const WaitNode<T>,
+    tail: *const WaitNode<T>,
+    len: usize,
+    /// Count of select signals in the list
+    selects: usize,
+}
+
+// Safety: the list only links signals, and signals are safe to move between threads
+unsafe impl<T> Send for WaitList<T> {}
+
+impl<T> WaitList<T> {
+    pub fn new() -> Self {
+        Self {
+            head: ptr::null(),
+            tail: ptr::null(),
+            len: 0,
+            selects: 0,
+        }
+    }
+
+    /// Returns count of signals in the list
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether a signal other than select signals is in the list
+    #[inline(always)]
+    pub fn has_waiter(&self) -> bool {
+        self.len > self.selects
+    }
+
+    /// Links the signal at the back of the list
+    /// Safety: the signal must stay alive and in place until it's unlinked, and it must not be linked in another list
+    #[inline(always)]
+    pub unsafe fn push_back(&mut self, signal: Signal<T>) {
+        let node = signal.node();
+        (*node).signal.set(signal);
+        (*node).prev.set(self.tail);
+        (*node).next.set(ptr::null());
+        (*node).linked.set(true);
+        if self.tail.is_null() {
+            self.head = node;
+        } else {
+            (*self.tail).next.set(node);
+        }
+        self.tail = node;
+        self.len += 1;
+        if signal.is_select() {
+            self.selects += 1;
+        }
+    }
+
+    /// Returns the signal at the front of the list
+    #[inline(always)]
+    pub fn front(&self) -> Option<Signal<T>> {
+        if self.head.is_null() {
+            return None;
+        }
+        // Safety: linked nodes are alive until they are unlinked
+        Some(unsafe { (*self.head).signal.get() })
+    }
+
+    /// Unlinks the signal at the front of the list and returns it
+    #[inline(always)]
+    pub fn pop_front(&mut self) -> Option<Signal<T>> {
+        let node = self.head;
+        if node.is_null() {
+            return None;
+        }
+        // Safety: linked nodes are alive until they are unlinked
+        unsafe {
+            self.unlink(node);
+            Some((*node).signal.get())
+        }
+    }
+
+    /// Unlinks the signal if it's in the list, returns whether it was in the list
+    /// Safety: the signal must be alive, and it must not be linked in another list
+    #[inline(always)]
+    pub unsafe fn remove(&mut self, signal: Signal<T>) -> bool {
+        let node = signal.node();
+        if !(*node).linked.get() {
+            return false;
+        }
+        self.unlink(node);
+        true
+    }
+
+    /// Returns whether the signal is in the list
+    /// Safety: the signal must be alive, and it must not be linked in another list
+    #[cfg(feature = "async")]
+    #[inline(always)]
+    pub unsafe fn contains(&self, signal: Signal<T>) -> bool {
+        (*signal.node()).linked.get()
+    }
+
+    #[inline(always)]
+    unsafe fn unlink(&mut self, node: *const WaitNode<T>) {
+        let (prev, next) = ((*node).prev.get(), (*node).next.get());
+        if prev.is_null() {
+            self.head = next;
+        } else {
+            (*prev).next.set(next);
+        }
+        if next.is_null() {
+            self.tail = prev;
+        } else {
+            (*next).prev.set(prev);
+        }
+        (*node).linked.set(false);
+        self.len -= 1;
+        if (*node).signal.get().is_select() {
+            self.selects -= 1;
+        }
+    }
+}
@@ -0,0 +1,635 @@
+use std::{
+    fmt,
+    fmt::Debug,
+    marker::PhantomData,
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+#[cfg(feature = "async")]
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+#[cfg(not(feature = "std-mutex"))]
+use crate::mutex::{Mutex, MutexGuard};
+#[cfg(feature = "std-mutex")]
+use std::sync::{Mutex, MutexGuard};
+
+use crate::{
+    pointer::KanalPtr, signal::*, waitlist::WaitList, ReceiveError, ReceiveErrorTimeout, SendError,
+};
+
+type WatchShared<T> = Arc<Mutex<WatchInternal<T>>>;
+
+/// Acquire mutex guard on watch internal for use in channel operations
+#[inline(always)]
+fn acquire_watch<T>(internal: &'_ WatchShared<T>) -> MutexGuard<'_, WatchInternal<T>> {
+    #[cfg(not(feature = "std-mutex"))]
+    return internal.lock();
+    #[cfg(feature = "std-mutex")]
+    internal.lock().unwrap()
+}
+
+/// Internal of the watch channel, it keeps the latest value and a version counter that is increased on every send,
+///  every receiver tracks the version that it has seen.
+struct WatchInternal<T> {
+    /// The latest sent value, it's shared with the references that are returned by `borrow`
+    value: Arc<T>,
+    /// Version of the value, it's increased on every send
+    version: u64,
+    /// Receivers that are waiting for a change, they are notified with an empty object
+    waiters: WaitList<()>,
+    /// Count of alive receivers
+    recv_count: u32,
+    /// Count of alive senders
+    send_count: u32,
+}
+
+impl<T> WatchInternal<T> {
+    fn new(initial: T) -> WatchShared<T> {
+        Arc::new(Mutex::from(Self {
+            value: Arc::new(initial),
+            version: 0,
+            waiters: WaitList::new(),
+            recv_count: 1,
+            send_count: 1,
+        }))
+    }
+
+    /// Checks whether the value changed since the `seen` version, and marks the current version as seen if it did
+    fn check_changed(&self, seen: &mut u64) -> Result<bool, ReceiveError> {
+        if self.recv_count == 0 {
+            return Err(ReceiveError::Closed);
+        }
+        if *seen != self.version {
+            *seen = self.version;
+            return Ok(true);
+        }
+        if self.send_count == 0 {
+            return Err(ReceiveError::SendClosed);
+        }
+        Ok(false)
+    }
+
+    /// Terminates waiting receivers, so they check the state of the channel again
+    fn terminate_waiters(&mut self) {
+        while let Some(sig) = self.waiters.pop_front() {
+            // Safety: it's safe to terminate owned signal once
+            unsafe { sig.terminate() }
+        }
+    }
+
+    /// Adds the signal of a receiver to the waiters
+    #[inline(always)]
+    fn push_waiter(&mut self, sig: Signal<()>) {
+        // Safety: waiters keep their signals in place until they are removed from the waiters
+        unsafe { self.waiters.push_back(sig) }
+    }
+
+    /// Removes the signal from the waiters, returns false if a sender already took it
+    #[inline(always)]
+    fn cancel_waiter(&mut self, sig: Signal<()>) -> bool {
+        // Safety: the owner of the signal cancels it, and it's only linked in the waiters of this channel
+        unsafe { self.waiters.remove(sig) }
+    }
+
+    /// Checks if the signal of a receiver is in the waiters
+    #[cfg(feature = "async")]
+    #[inline(always)]
+    fn waiter_exists(&self, sig: Signal<()>) -> bool {
+        // Safety: the owner of the signal checks it, and it's only linked in the waiters of this channel
+        unsafe { self.waiters.contains(sig) }
+    }
+}
+
+/// Reference to the value of a watch channel at the time of the borrow.
+/// The reference does not lock the channel, senders replace the value without waiting for it to be dropped.
+pub struct WatchRef<'a, T> {
+    value: Arc<T>,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> WatchRef<'a, T> {
+    fn new(value: Arc<T>) -> Self {
+        Self {
+            value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Deref for WatchRef<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T: Debug> Debug for WatchRef<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&*self.value, f)
+    }
+}
+
+macro_rules! watch_shared_impl {
+    () => {
+        /// Returns count of alive receiver instances of the channel
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::watch(0);
+        /// let receiver_clone=r.clone();
+        /// assert_eq!(s.receiver_count(),2);
+        /// ```
+        pub fn receiver_count(&self) -> u32 {
+            acquire_watch(&self.internal).recv_count
+        }
+        /// Returns count of alive sender instances of the channel
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::watch(0);
+        /// let sender_clone=s.clone();
+        /// assert_eq!(r.sender_count(),2);
+        /// ```
+        pub fn sender_count(&self) -> u32 {
+            acquire_watch(&self.internal).send_count
+        }
+        /// Closes the channel completely on both sides and terminates waiting receivers
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::watch(0);
+        /// s.close();
+        /// assert_eq!(r.is_closed(),true);
+        /// ```
+        pub fn close(&self) -> bool {
+            let mut internal = acquire_watch(&self.internal);
+            if internal.recv_count == 0 && internal.send_count == 0 {
+                return false;
+            }
+            internal.recv_count = 0;
+            internal.send_count = 0;
+            internal.terminate_waiters();
+            true
+        }
+        /// Returns whether the channel is closed on both side of send and receive or not
+        pub fn is_closed(&self) -> bool {
+            let internal = acquire_watch(&self.internal);
+            internal.send_count == 0 && internal.recv_count == 0
+        }
+    };
+}
+
+macro_rules! watch_recv_impl {
+    () => {
+        /// Returns a reference to the latest value without marking it as seen
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, r) = kanal::watch("init");
+        /// s.send("updated")?;
+        /// assert_eq!(*r.borrow(), "updated");
+        /// assert!(r.has_changed());
+        /// # anyhow::Ok(())
+        /// ```
+        pub fn borrow(&self) -> WatchRef<'_, T> {
+            WatchRef::new(acquire_watch(&self.internal).value.clone())
+        }
+        /// Returns a reference to the latest value and marks it as seen
+        /// # Examples
+        ///
+        /// ```
+        /// let (s, mut r) = kanal::watch("init");
+        /// s.send("updated")?;
+        /// assert_eq!(*r.borrow_and_update(), "updated");
+        /// assert!(!r.has_changed());
+        /// # anyhow::Ok(())
+        /// ```
+        pub fn borrow_and_update(&mut self) -> WatchRef<'_, T> {
+            let internal = acquire_watch(&self.internal);
+            self.seen = internal.version;
+            WatchRef::new(internal.value.clone())
+        }
+        /// Returns whether the value changed since it was last seen by this receiver
+        pub fn has_changed(&self) -> bool {
+            acquire_watch(&self.internal).version != self.seen
+        }
+        /// Returns whether the receive side is terminated, and no change is going to be observed in future
+        pub fn is_terminated(&self) -> bool {
+            let internal = acquire_watch(&self.internal);
+            internal.recv_count == 0 || (internal.send_count == 0 && internal.version == self.seen)
+        }
+    };
+}
+
+/// Sending side of a watch channel, every send overwrites the latest value and notifies the receivers.
+/// Sending never blocks, receivers that miss intermediate values only observe the latest one.
+/// The sender works in both sync and async contexts.
+/// # Examples
+///
+/// ```
+/// let (s, mut r) = kanal::watch(0);
+/// s.send(1)?;
+/// s.send(2)?;
+/// r.wait_changed()?;
+/// assert_eq!(*r.borrow(), 2);
+/// # anyhow::Ok(())
+/// ```
+pub struct WatchSender<T> {
+    internal: WatchShared<T>,
+}
+
+impl<T> WatchSender<T> {
+    /// Overwrites the value and notifies the receivers, the value is handed back inside the error if there is no receiver
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = kanal::watch(0);
+    /// drop(r);
+    /// assert_eq!(s.send(1), Err(kanal::SendError::ReceiveClosed(1)));
+    /// ```
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut internal = acquire_watch(&self.internal);
+        if internal.recv_count == 0 {
+            let send_count = internal.send_count;
+            drop(internal);
+            if send_count == 0 {
+                return Err(SendError::Closed(value));
+            }
+            return Err(SendError::ReceiveClosed(value));
+        }
+        let value = Arc::new(value);
+        let old = std::mem::replace(&mut internal.value, value);
+        internal.version += 1;
+        // async waiters are finished under the lock, so their futures never wait for the notification on drop
+        let mut handoffs: Vec<Handoff<()>> = Vec::with_capacity(internal.waiters.len());
+        while let Some(sig) = internal.waiters.pop_front() {
+            // Safety: it's safe to send to owned signal once
+            handoffs.push(unsafe { sig.send_locked(()) });
+        }
+        drop(internal);
+        // drop the old value outside of the lock
+        drop(old);
+        for handoff in handoffs {
+            handoff.finish();
+        }
+        Ok(())
+    }
+    /// Returns a reference to the latest value
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = kanal::watch(0);
+    /// s.send(1)?;
+    /// assert_eq!(*s.borrow(), 1);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn borrow(&self) -> WatchRef<'_, T> {
+        WatchRef::new(acquire_watch(&self.internal).value.clone())
+    }
+    /// Returns a new sync receiver that observes changes from now on
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = kanal::watch(0);
+    /// s.send(1)?;
+    /// let late = s.subscribe();
+    /// assert!(!late.has_changed());
+    /// assert_eq!(*late.borrow(), 1);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn subscribe(&self) -> WatchReceiver<T> {
+        let mut internal = acquire_watch(&self.internal);
+        if internal.send_count > 0 {
+            internal.recv_count += 1;
+        }
+        WatchReceiver {
+            internal: self.internal.clone(),
+            seen: internal.version,
+        }
+    }
+    /// Returns a new async receiver that observes changes from now on
+    #[cfg(feature = "async")]
+    pub fn subscribe_async(&self) -> WatchAsyncReceiver<T> {
+        self.subscribe().into_async()
+    }
+    /// Returns whether all receivers are dropped or not
+    pub fn is_disconnected(&self) -> bool {
+        acquire_watch(&self.internal).recv_count == 0
+    }
+    watch_shared_impl!();
+}
+
+impl<T> Clone for WatchSender<T> {
+    fn clone(&self) -> Self {
+        let mut internal = acquire_watch(&self.internal);
+        if internal.send_count > 0 {
+            internal.send_count += 1;
+        }
+        Self {
+            internal: self.internal.clone(),
+        }
+    }
+}
+
+impl<T> Drop for WatchSender<T> {
+    fn drop(&mut self) {
+        let mut internal = acquire_watch(&self.internal);
+        if internal.send_count > 0 {
+            internal.send_count -= 1;
+            if internal.send_count == 0 {
+                internal.terminate_waiters();
+            }
+        }
+    }
+}
+
+impl<T> Debug for WatchSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WatchSender {{ .. }}")
+    }
+}
+
+/// Receiving side of a watch channel in sync mode.
+/// Cloned receivers share the seen version of the original one at the time of cloning.
+pub struct WatchReceiver<T> {
+    internal: WatchShared<T>,
+    /// The version of the value that is seen by this receiver
+    seen: u64,
+}
+
+impl<T> WatchReceiver<T> {
+    /// Waits until the value changes since it was last seen, and marks the latest version as seen.
+    /// Every change is observed at least once, but several changes in between two calls are observed as one.
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::thread::spawn;
+    /// let (s, mut r) = kanal::watch(0);
+    /// let t = spawn(move || {
+    ///     r.wait_changed()?;
+    ///     anyhow::Ok(*r.borrow())
+    /// });
+    /// s.send(1)?;
+    /// assert_eq!(t.join().unwrap()?, 1);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn wait_changed(&mut self) -> Result<(), ReceiveError> {
+        loop {
+            let mut internal = acquire_watch(&self.internal);
+            if internal.check_changed(&mut self.seen)? {
+                return Ok(());
+            }
+            let sig = SyncSignal::<()>::new(KanalPtr::default());
+            let _sig_address_holder = &sig;
+            internal.push_waiter(sig.as_signal());
+            drop(internal);
+            // the result does not matter, the channel state is checked again after the wake up
+            let _ = sig.wait();
+        }
+    }
+    /// Waits until the value changes since it was last seen or the timeout elapses, and marks the latest version as seen.
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// let (s, mut r) = kanal::watch(0);
+    /// assert_eq!(
+    ///     r.wait_changed_timeout(Duration::from_millis(10)),
+    ///     Err(kanal::ReceiveErrorTimeout::Timeout)
+    /// );
+    /// s.send(1)?;
+    /// r.wait_changed_timeout(Duration::from_millis(10))?;
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn wait_changed_timeout(&mut self, duration: Duration) -> Result<(), ReceiveErrorTimeout> {
+        let deadline = Instant::now().checked_add(duration).unwrap();
+        loop {
+            let mut internal = acquire_watch(&self.internal);
+            match internal.check_changed(&mut self.seen) {
+                Ok(true) => return Ok(()),
+                Ok(false) => {}
+                Err(ReceiveError::Closed) => return Err(ReceiveErrorTimeout::Closed),
+                Err(ReceiveError::SendClosed) => return Err(ReceiveErrorTimeout::SendClosed),
+            }
+            if Instant::now() > deadline {
+                return Err(ReceiveErrorTimeout::Timeout);
+            }
+            let sig = SyncSignal::<()>::new(KanalPtr::default());
+            let _sig_address_holder = &sig;
+            internal.push_waiter(sig.as_signal());
+            drop(internal);
+            if !sig.wait_timeout(deadline) && !sig.is_terminated() {
+                {
+                    let mut internal = acquire_watch(&self.internal);
+                    if internal.cancel_waiter(sig.as_signal()) {
+                        return Err(ReceiveErrorTimeout::Timeout);
+                    }
+                }
+                // a sender popped the signal, wait for the notification before releasing the signal
+                let _ = sig.wait();
+            }
+        }
+    }
+    watch_recv_impl!();
+    /// Clones the receiver as the async version of it, the clone shares the seen version of this receiver
+    #[cfg(feature = "async")]
+    pub fn clone_async(&self) -> WatchAsyncReceiver<T> {
+        self.clone().into_async()
+    }
+    /// Converts the receiver to the async version of it
+    #[cfg(feature = "async")]
+    pub(crate) fn into_async(self) -> WatchAsyncReceiver<T> {
+        let this = std::mem::ManuallyDrop::new(self);
+        WatchAsyncReceiver {
+            // Safety: self is not going to be dropped, so the internal is moved out of it
+            internal: unsafe { std::ptr::read(&this.internal) },
+            seen: this.seen,
+        }
+    }
+    watch_shared_impl!();
+}
+
+/// Receiving side of a watch channel in async mode.
+/// Cloned receivers share the seen version of the original one at the time of cloning.
+#[cfg(feature = "async")]
+pub struct WatchAsyncReceiver<T> {
+    internal: WatchShared<T>,
+    /// The version of the value that is seen by this receiver
+    seen: u64,
+}
+
+#[cfg(feature = "async")]
+impl<T> WatchAsyncReceiver<T> {
+    /// Returns a future that resolves when the value changes since it was last seen, and marks the latest version as seen.
+    /// Every change is observed at least once, but several changes in between two calls are observed as one.
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let (s, mut r) = kanal::watch_async("init");
+    /// tokio::spawn(async move {
+    ///     s.send("updated")
+    /// });
+    /// r.changed().await?;
+    /// assert_eq!(*r.borrow(), "updated");
+    /// # anyhow::Ok(())
+    /// # });
+    /// ```
+    pub fn changed(&mut self) -> WatchChangedFuture<'_, T> {
+        WatchChangedFuture {
+            receiver: self,
+            waiting: false,
+            sig: None,
+        }
+    }
+    watch_recv_impl!();
+    /// Clones the receiver as the sync version of it, the clone shares the seen version of this receiver
+    pub fn clone_sync(&self) -> WatchReceiver<T> {
+        let mut internal = acquire_watch(&self.internal);
+        if internal.recv_count > 0 {
+            internal.recv_count += 1;
+        }
+        WatchReceiver {
+            internal: self.internal.clone(),
+            seen: self.seen,
+        }
+    }
+    watch_shared_impl!();
+}
+
+impl<T> Clone for WatchReceiver<T> {
+    fn clone(&self) -> Self {
+        let mut internal = acquire_watch(&self.internal);
+        if internal.recv_count > 0 {
+            internal.recv_count += 1;
+        }
+        Self {
+            internal: self.internal.clone(),
+            seen: self.seen,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Clone for WatchAsyncReceiver<T> {
+    fn clone(&self) -> Self {
+        self.clone_sync().into_async()
+    }
+}
+
+/// Drops a receiver of the watch channel
+fn drop_receiver<T>(internal: &WatchShared<T>) {
+    let mut internal = acquire_watch(internal);
+    if internal.recv_count > 0 {
+        internal.recv_count -= 1;
+    }
+}
+
+impl<T> Drop for WatchReceiver<T> {
+    fn drop(&mut self) {
+        drop_receiver(&self.internal);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Drop for WatchAsyncReceiver<T> {
+    fn drop(&mut self) {
+        drop_receiver(&self.internal);
+    }
+}
+
+impl<T> Debug for WatchReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WatchReceiver {{ .. }}")
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Debug for WatchAsyncReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WatchAsyncReceiver {{ .. }}")
+    }
+}
+
+/// Change future of a watch channel, it must be polled to wait for the change
+#[cfg(feature = "async")]
+#[must_use = "futures do nothing unless you .await or poll them"]
+pub struct WatchChangedFuture<'a, T> {
+    receiver: &'a mut WatchAsyncReceiver<T>,
+    waiting: bool,
+    /// The signal is boxed as its address is shared with the waiters of the channel, so the future can be moved freely
+    sig: Option<Box<AsyncSignal<()>>>,
+}
+
+#[cfg(feature = "async")]
+impl<'a, T> Future for WatchChangedFuture<'a, T> {
+    type Output = Result<(), ReceiveError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut internal = acquire_watch(&this.receiver.internal);
+        if this.waiting {
+            let sig = this.sig.as_mut().unwrap();
+            if internal.waiter_exists(sig.as_signal()) {
+                // the signal is only read by senders under the lock, so it's safe to update the waker here
+                if !sig.will_wake(cx.waker()) {
+                    sig.register(cx.waker());
+                }
+                return Poll::Pending;
+            }
+            // the signal is finished by a sender under the lock, check the channel again
+            this.waiting = false;
+        }
+        match internal.check_changed(&mut this.receiver.seen) {
+            Ok(true) => return Poll::Ready(Ok(())),
+            Ok(false) => {}
+            Err(err) => return Poll::Ready(Err(err)),
+        }
+        let sig = match &mut this.sig {
+            Some(sig) => {
+                **sig = AsyncSignal::new();
+                sig
+            }
+            None => this.sig.insert(Box::new(AsyncSignal::new())),
+        };
+        sig.register(cx.waker());
+        internal.push_waiter(sig.as_signal());
+        this.waiting = true;
+        Poll::Pending
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, T> Drop for WatchChangedFuture<'a, T> {
+    fn drop(&mut self) {
+        if self.waiting {
+            // if a sender already took the signal, it's finished under the lock and the sender only holds the waker now
+            acquire_watch(&self.receiver.internal)
+                .cancel_waiter(self.sig.as_ref().unwrap().as_signal());
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, T> Debug for WatchChangedFuture<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WatchChangedFuture {{ .. }}")
+    }
+}
+
+/// Returns sender and sync receiver of a watch channel with the initial value
+pub(crate) fn new_watch<T>(initial: T) -> (WatchSender<T>, WatchReceiver<T>) {
+    let internal = WatchInternal::new(initial);
+    (
+        WatchSender {
+            internal: internal.clone(),
+        },
+        WatchReceiver { internal, seen: 0 },
+    )
+}
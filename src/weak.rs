@@ -0,0 +1,120 @@
+use std::{fmt, sync::Arc};
+
+use crate::debug_channel;
+use crate::internal::{acquire_internal, WeakInternal};
+#[cfg(feature = "async")]
+use crate::{AsyncReceiver, AsyncSender};
+use crate::{Receiver, Sender};
+
+macro_rules! weak_endpoint {
+    ($(#[$attr:meta])* $weak:ident, $endpoint:ident, $count:ident) => {
+        $(#[$attr])*
+        pub struct $weak<T> {
+            internal: WeakInternal<T>,
+        }
+
+        impl<T> $endpoint<T> {
+            #[doc = concat!("Returns a [`", stringify!($weak), "`] of the channel, weak handles are not counted as alive endpoints")]
+            ///  so they don't keep the channel open.
+            pub fn downgrade(&self) -> $weak<T> {
+                $weak {
+                    internal: Arc::downgrade(&self.internal),
+                }
+            }
+        }
+
+        impl<T> $weak<T> {
+            #[doc = concat!("Returns a [`", stringify!($endpoint), "`] of the channel, or `None` if this side of the channel is closed")]
+            ///  or all of the endpoints are dropped.
+            pub fn upgrade(&self) -> Option<$endpoint<T>> {
+                let internal = self.internal.upgrade()?;
+                {
+                    let mut guard = acquire_internal(&internal);
+                    if guard.$count == 0 {
+                        return None;
+                    }
+                    guard.$count += 1;
+                }
+                Some($endpoint::new(internal))
+            }
+        }
+
+        impl<T> Clone for $weak<T> {
+            fn clone(&self) -> Self {
+                Self {
+                    internal: self.internal.clone(),
+                }
+            }
+        }
+
+        impl<T> fmt::Debug for $weak<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.internal.upgrade() {
+                    Some(internal) => debug_channel(f, stringify!($weak), &internal),
+                    None => write!(f, "{} {{ .. }}", stringify!($weak)),
+                }
+            }
+        }
+    };
+}
+
+weak_endpoint!(
+    /// Weak handle of the sending side of a channel that is created with [`Sender::downgrade`],
+    ///  it doesn't count as a sender, so the channel is closed once all of the senders are dropped
+    ///  regardless of weak handles.
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = kanal::unbounded::<u64>();
+    /// let weak = s.downgrade();
+    /// assert_eq!(r.sender_count(), 1);
+    /// weak.upgrade().unwrap().send(1)?;
+    /// assert_eq!(r.recv()?, 1);
+    /// drop(s);
+    /// assert!(r.is_disconnected());
+    /// assert!(weak.upgrade().is_none());
+    /// # anyhow::Ok(())
+    /// ```
+    WeakSender,
+    Sender,
+    send_count
+);
+
+weak_endpoint!(
+    /// Weak handle of the receiving side of a channel that is created with [`Receiver::downgrade`],
+    ///  it doesn't count as a receiver, so the channel is closed once all of the receivers are dropped
+    ///  regardless of weak handles.
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = kanal::unbounded::<u64>();
+    /// let weak = r.downgrade();
+    /// s.send(1)?;
+    /// assert_eq!(weak.upgrade().unwrap().recv()?, 1);
+    /// drop(r);
+    /// assert!(s.is_disconnected());
+    /// assert!(weak.upgrade().is_none());
+    /// # anyhow::Ok(())
+    /// ```
+    WeakReceiver,
+    Receiver,
+    recv_count
+);
+
+#[cfg(feature = "async")]
+weak_endpoint!(
+    /// Weak handle of the async sending side of a channel that is created with [`AsyncSender::downgrade`],
+    ///  see [`WeakSender`]
+    WeakAsyncSender,
+    AsyncSender,
+    send_count
+);
+
+#[cfg(feature = "async")]
+weak_endpoint!(
+    /// Weak handle of the async receiving side of a channel that is created with [`AsyncReceiver::downgrade`],
+    ///  see [`WeakReceiver`]
+    WeakAsyncReceiver,
+    AsyncReceiver,
+    recv_count
+);
@@ -1,9 +1,15 @@
+#![allow(dead_code, clippy::unnecessary_cast, clippy::let_underscore_future)]
 mod common;
 
 use common::*;
 use futures_core::FusedStream;
 
-use kanal::{bounded_async, unbounded_async, AsyncReceiver, AsyncSender, ReceiveError, SendError};
+use kanal::{
+    bounded_async, bounded_async_with_overflow, broadcast_async, oneshot, oneshot_async,
+    priority_bounded_async, unbounded_async, watch_async, AsyncReceiver, AsyncSender,
+    BroadcastReceiveError, Builder, OverflowPolicy, ReceiveError, ReceiveErrorTimeout, SendError,
+    SendErrorTimeout, SendManyError, TryRecvError, TrySendError,
+};
 
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -267,14 +273,14 @@ async fn async_recv_from_closed_channel_queue() {
 async fn async_send_to_half_closed_channel() {
     let (tx, rx) = new_async(Some(1));
     drop(rx);
-    assert_eq!(tx.send(1).await.err().unwrap(), SendError::ReceiveClosed);
+    assert_eq!(tx.send(1).await.err().unwrap(), SendError::ReceiveClosed(1));
 }
 
 #[tokio::test]
 async fn async_send_to_closed_channel() {
     let (tx, rx) = new_async(Some(1));
     rx.close();
-    assert_eq!(tx.send(1).await.err().unwrap(), SendError::Closed);
+    assert_eq!(tx.send(1).await.err().unwrap(), SendError::Closed(1));
 }
 
 // Drop tests
@@ -472,6 +478,977 @@ async fn async_stream() {
     assert_eq!(stream.next().await, None);
 }
 
+#[tokio::test]
+async fn async_send_timeout() {
+    let (s, r) = new_async(Some(0));
+    assert_eq!(
+        s.send_timeout(1, Duration::from_millis(50)).await,
+        Err(SendErrorTimeout::Timeout(1))
+    );
+    tokio::spawn(async move {
+        assert_eq!(r.recv().await.unwrap(), 2);
+    });
+    s.send_timeout(2, Duration::from_secs(10)).await.unwrap();
+}
+
+#[tokio::test]
+async fn async_send_returns_data_on_close() {
+    let (s, r) = new_async(Some(0));
+    let handle = tokio::spawn(async move { s.send(String::from("not delivered")).await });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    r.close();
+    let err = handle.await.unwrap().err().unwrap();
+    assert_eq!(err, SendError::Closed(String::from("not delivered")));
+}
+
+#[tokio::test]
+async fn async_send_timeout_returns_data() {
+    let (s, _r) = new_async(Some(0));
+    let counter = Arc::new(AtomicUsize::new(0));
+    let data = s
+        .send_timeout(
+            DropTester::new(counter.clone(), 42),
+            Duration::from_millis(10),
+        )
+        .await
+        .err()
+        .unwrap()
+        .into_inner();
+    assert_eq!(data.i, 42);
+    assert_eq!(counter.load(Ordering::SeqCst), 0_usize);
+    drop(data);
+    assert_eq!(counter.load(Ordering::SeqCst), 1_usize);
+}
+
+#[tokio::test]
+async fn async_recv_timeout() {
+    let (s, r) = new_async(Some(0));
+    assert_eq!(
+        r.recv_timeout(Duration::from_millis(50)).await,
+        Err(ReceiveErrorTimeout::Timeout)
+    );
+    tokio::spawn(async move {
+        s.send(1).await.unwrap();
+    });
+    assert_eq!(r.recv_timeout(Duration::from_secs(10)).await.unwrap(), 1);
+    assert_eq!(
+        r.recv_timeout(Duration::from_secs(10)).await,
+        Err(ReceiveErrorTimeout::SendClosed)
+    );
+}
+
+#[tokio::test]
+async fn async_recv_timeout_cancel_many() {
+    let (_s, r) = new_async::<usize>(Some(0));
+    for _ in 0..100 {
+        assert!(tokio::time::timeout(
+            Duration::from_micros(10),
+            r.recv_timeout(Duration::from_secs(3600))
+        )
+        .await
+        .is_err());
+    }
+    // canceled deadlines are discarded without holding back the nearer ones
+    assert_eq!(
+        r.recv_timeout(Duration::from_millis(10)).await,
+        Err(ReceiveErrorTimeout::Timeout)
+    );
+}
+
+#[tokio::test]
+async fn async_send_timeout_drop_test() {
+    let (s, _r) = new_async(Some(0));
+    let counter = Arc::new(AtomicUsize::new(0));
+    for _ in 0..10 {
+        let res = s
+            .send_timeout(
+                DropTester::new(counter.clone(), 1234),
+                Duration::from_millis(1),
+            )
+            .await;
+        assert!(matches!(res, Err(SendErrorTimeout::Timeout(_))));
+    }
+    assert_eq!(counter.load(Ordering::SeqCst), 10_usize);
+}
+
+#[tokio::test]
+async fn async_recv_timeout_mpmc() {
+    let (s, r) = new_async(Some(0));
+    let mut list = Vec::new();
+    for _ in 0..THREADS {
+        let s = s.clone();
+        list.push(tokio::spawn(async move {
+            for i in 0..MESSAGES / THREADS {
+                s.send_timeout(i, Duration::from_secs(10)).await.unwrap();
+            }
+        }));
+    }
+    for _ in 0..THREADS {
+        let r = r.clone();
+        list.push(tokio::spawn(async move {
+            for _ in 0..MESSAGES / THREADS {
+                r.recv_timeout(Duration::from_secs(10)).await.unwrap();
+            }
+        }));
+    }
+    for h in list {
+        h.await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn async_sink_forward() {
+    use futures::stream::StreamExt;
+    let (s, r) = new_async(Some(0));
+    let (s2, r2) = new_async(Some(0));
+    tokio::spawn(async move {
+        for i in 0..MESSAGES {
+            s.send(i).await.unwrap();
+        }
+    });
+    tokio::spawn(async move {
+        r.stream().map(Ok).forward(s2.into_sink()).await.unwrap();
+    });
+    let mut stream = r2.stream();
+    for i in 0..MESSAGES {
+        assert_eq!(stream.next().await.unwrap(), i);
+    }
+    // forward closes the sink, so the second channel gets closed from the send side
+    assert_eq!(stream.next().await, None);
+}
+
+#[tokio::test]
+async fn async_sink_backpressure() {
+    use futures::sink::SinkExt;
+    let (s, r) = new_async(Some(1));
+    let mut sink = s.into_sink();
+    sink.send(1).await.unwrap();
+    // the queue is full, so the sink is not ready until the receiver makes room
+    assert!(tokio::time::timeout(
+        Duration::from_millis(100),
+        std::future::poll_fn(|cx| sink.poll_ready_unpin(cx))
+    )
+    .await
+    .is_err());
+    let h = tokio::spawn(async move {
+        sink.send(2).await.unwrap();
+        sink.close().await.unwrap();
+        assert_eq!(sink.send(3).await, Err(SendError::Closed(None)));
+    });
+    assert_eq!(r.recv().await.unwrap(), 1);
+    assert_eq!(r.recv().await.unwrap(), 2);
+    h.await.unwrap();
+    assert_eq!(r.recv().await, Err(ReceiveError::SendClosed));
+}
+
+#[tokio::test]
+async fn async_sink_ready_on_receiver() {
+    use futures::sink::SinkExt;
+    let (s, r) = new_async(Some(0));
+    let mut sink = s.into_sink();
+    let mut ready = std::future::poll_fn(|cx| sink.poll_ready_unpin(cx));
+    assert!(futures::poll!(&mut ready).is_pending());
+    // a waiting receiver makes a zero capacity channel ready
+    let h = tokio::spawn(async move { r.recv().await });
+    ready.await.unwrap();
+    sink.send(1).await.unwrap();
+    assert_eq!(h.await.unwrap(), Ok(1));
+    assert_eq!(
+        std::future::poll_fn(|cx| sink.poll_ready_unpin(cx)).await,
+        Err(SendError::ReceiveClosed(None))
+    );
+}
+
+#[tokio::test]
+async fn async_sink_close() {
+    use futures::sink::SinkExt;
+    let (s, r) = new_async::<i32>(Some(0));
+    let other = s.clone();
+    let mut sink = s.into_sink();
+    sink.close().await.unwrap();
+    // the send side is still open with another sender
+    assert!(!r.is_disconnected());
+    let h = tokio::spawn(async move { r.recv().await });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    // the waiting receiver is woken when the last sender is gone
+    drop(other);
+    assert_eq!(h.await.unwrap(), Err(ReceiveError::SendClosed));
+}
+
+#[tokio::test]
+async fn async_sink_drop_test() {
+    use futures::sink::SinkExt;
+    let (s, r) = new_async(Some(0));
+    let counter = Arc::new(AtomicUsize::new(0));
+    let mut sink = s.into_sink();
+    // the object waits in the waitlist as no receiver is waiting
+    sink.start_send_unpin(DropTester::new(counter.clone(), 1234))
+        .unwrap();
+    assert!(
+        tokio::time::timeout(Duration::from_millis(50), sink.flush())
+            .await
+            .is_err()
+    );
+    drop(sink);
+    assert_eq!(counter.load(Ordering::SeqCst), 1_usize);
+    drop(r);
+}
+
+#[tokio::test]
+async fn async_owned_futures() {
+    let (s, r) = new_async(Some(0));
+    let receivers: Vec<_> = (0..THREADS).map(|_| tokio::spawn(r.recv_owned())).collect();
+    let senders: Vec<_> = (0..THREADS)
+        .map(|i| tokio::spawn(s.send_owned(i)))
+        .collect();
+    for h in senders {
+        h.await.unwrap().unwrap();
+    }
+    let mut received: Vec<_> = Vec::new();
+    for h in receivers {
+        received.push(h.await.unwrap().unwrap());
+    }
+    received.sort();
+    // owned futures do not count as channel handles, so the channel closes with its last handle
+    drop(s);
+    assert_eq!(r.recv_owned().await, Err(ReceiveError::SendClosed));
+    assert_eq!(received, (0..THREADS).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn async_owned_stream() {
+    use futures::stream::StreamExt;
+    let (s, r) = new_async(Some(1));
+    let handle = tokio::spawn(async move {
+        let mut stream = r.into_stream();
+        let mut count = 0;
+        while let Some(v) = stream.next().await {
+            assert_eq!(v, count);
+            count += 1;
+        }
+        assert!(stream.is_terminated());
+        count
+    });
+    for i in 0..MESSAGES {
+        s.send(i).await.unwrap();
+    }
+    drop(s);
+    assert_eq!(handle.await.unwrap(), MESSAGES);
+}
+
+#[tokio::test]
+async fn async_owned_drop_test() {
+    use futures::stream::StreamExt;
+    let (s, r) = new_async(Some(0));
+    let counter = Arc::new(AtomicUsize::new(0));
+    let handle = tokio::spawn(s.send_owned(DropTester::new(counter.clone(), 1234)));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    handle.abort();
+    assert!(handle.await.unwrap_err().is_cancelled());
+    assert_eq!(counter.load(Ordering::SeqCst), 1_usize);
+    // the receiver taken back from a stream with a pending receive is still usable
+    let mut stream = r.into_stream();
+    assert!(
+        tokio::time::timeout(Duration::from_millis(50), stream.next())
+            .await
+            .is_err()
+    );
+    let r = stream.into_inner();
+    assert_eq!(r.receiver_count(), 1);
+    tokio::spawn(s.send_owned(DropTester::new(counter.clone(), 1234)));
+    drop(r.recv().await.unwrap());
+    assert_eq!(counter.load(Ordering::SeqCst), 2_usize);
+}
+
+#[tokio::test]
+async fn async_oneshot() {
+    for _ in 0..MESSAGES / 100 {
+        let (s, r) = oneshot_async();
+        let (s2, r2) = oneshot_async();
+        tokio::spawn(async move {
+            s.send(1u8).unwrap();
+            s2.send((0u64, !0u64, 0u64, !0u64)).unwrap();
+        });
+        assert_eq!(r.await.unwrap(), 1);
+        assert_eq!(r2.await.unwrap(), (0u64, !0u64, 0u64, !0u64));
+    }
+}
+
+#[tokio::test]
+async fn async_oneshot_sync_interop() {
+    let (s, r) = oneshot();
+    let handle = tokio::spawn(r.to_async());
+    std::thread::spawn(move || s.send(String::from("from thread")).unwrap());
+    assert_eq!(handle.await.unwrap().unwrap(), "from thread");
+    // a polled async receiver can turn back into a sync one
+    let (s, mut r) = oneshot_async();
+    assert!(tokio::time::timeout(Duration::from_millis(10), &mut r)
+        .await
+        .is_err());
+    s.send(1).unwrap();
+    assert_eq!(r.to_sync().recv().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn async_oneshot_closed() {
+    let (s, r) = oneshot_async::<u64>();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        drop(s);
+    });
+    assert_eq!(r.await, Err(ReceiveError::SendClosed));
+    // the receiver is dropped while it's waiting
+    let (s, mut r) = oneshot_async();
+    assert!(tokio::time::timeout(Duration::from_millis(10), &mut r)
+        .await
+        .is_err());
+    drop(r);
+    assert_eq!(s.send(1), Err(SendError::ReceiveClosed(1)));
+}
+
+#[tokio::test]
+async fn async_oneshot_drop_test() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let (s, mut r) = oneshot_async();
+    assert!(tokio::time::timeout(Duration::from_millis(10), &mut r)
+        .await
+        .is_err());
+    // the object is handed to the registered signal of the receiver
+    s.send(DropTester::new(counter.clone(), 10)).unwrap();
+    assert_eq!(counter.load(Ordering::SeqCst), 0_usize);
+    drop(r);
+    assert_eq!(counter.load(Ordering::SeqCst), 1_usize);
+}
+
+#[tokio::test]
+async fn async_oneshot_drop_while_sending() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    for i in 1..=MESSAGES / 100 {
+        let (s, mut r) = oneshot_async();
+        assert!(futures::poll!(&mut r).is_pending());
+        let object = DropTester::new(counter.clone(), i);
+        let t = std::thread::spawn(move || s.send(object).map_err(|err| err.into_inner()));
+        // the drop never waits for the sender, the sender takes the object back if it's not delivered
+        drop(r);
+        drop(t.join().unwrap());
+    }
+    assert_eq!(counter.load(Ordering::SeqCst), MESSAGES / 100);
+}
+
+#[tokio::test]
+async fn async_broadcast() {
+    let (s, mut r) = broadcast_async(MESSAGES);
+    let mut sync_receiver = r.clone_sync();
+    let sync_handle = std::thread::spawn(move || {
+        for i in 0..MESSAGES {
+            assert_eq!(sync_receiver.recv().unwrap(), i);
+        }
+    });
+    let mut handles = Vec::new();
+    for _ in 0..THREADS {
+        let mut r = r.clone();
+        handles.push(tokio::spawn(async move {
+            for i in 0..MESSAGES {
+                assert_eq!(r.recv().await.unwrap(), i);
+            }
+            assert_eq!(r.recv().await, Err(BroadcastReceiveError::SendClosed));
+        }));
+    }
+    tokio::spawn(async move {
+        for i in 0..MESSAGES {
+            s.send(i).unwrap();
+        }
+    });
+    for i in 0..MESSAGES {
+        assert_eq!(r.recv().await.unwrap(), i);
+    }
+    for h in handles {
+        h.await.unwrap();
+    }
+    sync_handle.join().unwrap();
+}
+
+#[tokio::test]
+async fn async_broadcast_drop_waiting_future() {
+    let (s, mut r) = broadcast_async(1);
+    assert!(tokio::time::timeout(Duration::from_millis(10), r.recv())
+        .await
+        .is_err());
+    s.send(1).unwrap();
+    s.send(2).unwrap();
+    assert_eq!(r.recv().await, Err(BroadcastReceiveError::Lagged(1)));
+    assert_eq!(r.recv().await, Ok(2));
+}
+
+#[tokio::test]
+async fn async_broadcast_move_waiting_future() {
+    let (s, mut r) = broadcast_async(1);
+    let mut r2 = r.clone();
+    let mut f = r.recv();
+    assert!(futures::poll!(&mut f).is_pending());
+    // the future is registered as a waiter, moving it must not invalidate the registration
+    let f = Box::new(f);
+    let mut f2 = r2.recv();
+    assert!(futures::poll!(&mut f2).is_pending());
+    let f2 = Box::new(f2);
+    drop(f2);
+    s.send(1).unwrap();
+    assert_eq!(f.await, Ok(1));
+    assert_eq!(r2.recv().await, Ok(1));
+}
+
+#[tokio::test]
+async fn async_watch_move_waiting_future() {
+    let (s, mut r) = watch_async(0);
+    let mut r2 = r.clone();
+    let mut f = r.changed();
+    assert!(futures::poll!(&mut f).is_pending());
+    // the future is registered as a waiter, moving it must not invalidate the registration
+    let f = Box::new(f);
+    let mut f2 = r2.changed();
+    assert!(futures::poll!(&mut f2).is_pending());
+    let f2 = Box::new(f2);
+    drop(f2);
+    s.send(1).unwrap();
+    assert_eq!(f.await, Ok(()));
+    assert_eq!(*r.borrow(), 1);
+    assert_eq!(r2.changed().await, Ok(()));
+}
+
+#[tokio::test]
+async fn async_watch() {
+    let (s, mut r) = watch_async(0);
+    let mut sync_receiver = r.clone_sync();
+    let sync_handle = std::thread::spawn(move || {
+        while sync_receiver.wait_changed().is_ok() {}
+        assert_eq!(*sync_receiver.borrow(), MESSAGES);
+    });
+    let mut handles = Vec::new();
+    for _ in 0..THREADS {
+        let mut r = r.clone();
+        handles.push(tokio::spawn(async move {
+            while r.changed().await.is_ok() {}
+            assert_eq!(*r.borrow(), MESSAGES);
+        }));
+    }
+    tokio::spawn(async move {
+        for i in 1..=MESSAGES {
+            s.send(i).unwrap();
+        }
+    });
+    assert_eq!(r.changed().await, Ok(()));
+    for h in handles {
+        h.await.unwrap();
+    }
+    sync_handle.join().unwrap();
+    assert_eq!(r.changed().await, Err(ReceiveError::SendClosed));
+}
+
+#[tokio::test]
+async fn async_watch_drop_waiting_future() {
+    let (s, mut r) = watch_async(0);
+    assert!(tokio::time::timeout(Duration::from_millis(10), r.changed())
+        .await
+        .is_err());
+    assert!(!r.has_changed());
+    s.send(1).unwrap();
+    assert!(r.has_changed());
+    assert_eq!(r.changed().await, Ok(()));
+    assert_eq!(*r.borrow(), 1);
+}
+
+#[tokio::test]
+async fn async_priority() {
+    let (s, r) = priority_bounded_async(4);
+    let sync_sender = s.clone_sync();
+    for v in [1, 4, 2, 3] {
+        s.send(v).await.unwrap();
+    }
+    // senders that wait for capacity are ordered once their objects are buffered
+    let handle = std::thread::spawn(move || sync_sender.send(5).unwrap());
+    assert_eq!(r.recv().await.unwrap(), 4);
+    handle.join().unwrap();
+    for v in [5, 3, 2, 1] {
+        assert_eq!(r.recv().await.unwrap(), v);
+    }
+    let handle = tokio::spawn(async move { r.recv().await.unwrap() });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    s.send(6).await.unwrap();
+    assert_eq!(handle.await.unwrap(), 6);
+}
+
+#[tokio::test]
+async fn async_overflow() {
+    let (s, r) = bounded_async_with_overflow(10, OverflowPolicy::DropOldest);
+    for i in 0..MESSAGES {
+        s.send(i).await.unwrap();
+    }
+    assert_eq!(s.evicted_count(), (MESSAGES - 10) as u64);
+    for i in MESSAGES - 10..MESSAGES {
+        assert_eq!(r.recv().await.unwrap(), i);
+    }
+}
+
+#[tokio::test]
+async fn async_builder_mixed_endpoints() {
+    let (s, r) = Builder::new().bounded(1).name("mixed").build_sync_async();
+    let h = std::thread::spawn(move || {
+        for i in 0..MESSAGES {
+            s.send(i).unwrap();
+        }
+    });
+    for i in 0..MESSAGES {
+        assert_eq!(r.recv().await.unwrap(), i);
+    }
+    h.join().unwrap();
+    assert_eq!(format!("{:?}", r), r#"AsyncReceiver { name: "mixed", .. }"#);
+
+    let (s, r) = Builder::new().bounded(0).build_async_sync();
+    let h = std::thread::spawn(move || r.recv().unwrap());
+    s.send(1).await.unwrap();
+    assert_eq!(h.join().unwrap(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn async_recv_any_no_lost_messages() {
+    let channels: Vec<_> = (0..4).map(|i| new_async::<usize>(Some(i))).collect();
+    let mut list = Vec::new();
+    for (s, _) in &channels {
+        for _ in 0..THREADS {
+            let s = s.clone();
+            list.push(tokio::spawn(async move {
+                for _ in 0..MESSAGES / THREADS {
+                    s.send(1).await.unwrap();
+                }
+            }));
+        }
+    }
+    let receivers: Vec<_> = channels.iter().map(|(_, r)| r).collect();
+    let mut counts = [0; 4];
+    for _ in 0..MESSAGES / THREADS * THREADS * 4 {
+        // a timeout cancels the future while it's waiting, it must not lose messages
+        if let Ok((index, res)) =
+            tokio::time::timeout(Duration::from_micros(50), kanal::recv_any(&receivers)).await
+        {
+            counts[index] += res.unwrap();
+        } else {
+            let (index, res) = kanal::recv_any(&receivers).await;
+            counts[index] += res.unwrap();
+        }
+    }
+    for h in list {
+        h.await.unwrap();
+    }
+    assert_eq!(counts, [MESSAGES / THREADS * THREADS; 4]);
+    for r in receivers {
+        assert!(r.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn async_recv_any_fairness() {
+    let (s1, r1) = unbounded_async();
+    let (s2, r2) = unbounded_async();
+    for _ in 0..1000 {
+        s1.send(0).await.unwrap();
+        s2.send(1).await.unwrap();
+    }
+    let mut counts = [0; 2];
+    for _ in 0..1000 {
+        let (index, res) = kanal::recv_any(&[&r1, &r2]).await;
+        assert_eq!(res.unwrap(), index);
+        counts[index] += 1;
+    }
+    assert!(counts[0] > 250 && counts[1] > 250, "{:?}", counts);
+}
+
+#[tokio::test]
+async fn async_recv_any_closed() {
+    let (s1, r1) = bounded_async::<u64>(0);
+    let (s2, r2) = bounded_async::<u64>(0);
+    let handle = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(s2);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        s1.send(1).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(s1);
+    });
+    // the closed receiver is skipped while the other one is open
+    assert_eq!(kanal::recv_any(&[&r1, &r2]).await, (0, Ok(1)));
+    assert_eq!(
+        kanal::recv_any(&[&r1, &r2]).await,
+        (0, Err(ReceiveError::SendClosed))
+    );
+    handle.await.unwrap();
+}
+
+#[test]
+#[should_panic(expected = "recv_any without receivers")]
+fn async_recv_any_empty() {
+    let _ = kanal::recv_any::<u64>(&[]);
+}
+
+#[tokio::test]
+async fn async_send_many() {
+    for cap in [Some(0), Some(1), Some(100), None] {
+        let (s, r) = new_async(cap);
+        let h = tokio::spawn(async move {
+            for i in 0..MESSAGES {
+                assert_eq!(r.recv().await.unwrap(), i);
+            }
+        });
+        assert_eq!(s.send_many(0..MESSAGES).await.unwrap(), MESSAGES);
+        h.await.unwrap();
+    }
+    let (s, r) = bounded_async::<u64>(1);
+    drop(r);
+    assert_eq!(
+        s.send_many(0..10).await,
+        Err(SendManyError::ReceiveClosed(0, Some(0)))
+    );
+}
+
+#[tokio::test]
+async fn async_recv_many() {
+    for cap in [Some(0), Some(1), Some(100), None] {
+        let (s, r) = new_async(cap);
+        let h = tokio::spawn(async move {
+            for i in 0..MESSAGES {
+                s.send(i).await.unwrap();
+            }
+        });
+        let mut buf = Vec::new();
+        while buf.len() < MESSAGES {
+            let count = r.recv_many(&mut buf, 64).await.unwrap();
+            assert!(count > 0 && count <= 64);
+        }
+        assert!(buf.iter().copied().eq(0..MESSAGES));
+        h.await.unwrap();
+        assert_eq!(
+            r.recv_many(&mut buf, 64).await,
+            Err(ReceiveError::SendClosed)
+        );
+    }
+}
+
+#[tokio::test]
+async fn async_recv_batch_timeout() {
+    let (s, r) = bounded_async::<u64>(0);
+    let h = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        s.send(1).await.unwrap();
+        s.send(2).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        s.send(3).await.unwrap();
+    });
+    assert_eq!(
+        r.recv_batch_timeout(10, Duration::from_millis(100))
+            .await
+            .unwrap(),
+        vec![1, 2]
+    );
+    assert_eq!(
+        r.recv_batch_timeout(10, Duration::from_millis(10))
+            .await
+            .unwrap(),
+        vec![3]
+    );
+    h.await.unwrap();
+    assert_eq!(
+        r.recv_batch_timeout(10, Duration::from_millis(10)).await,
+        Err(ReceiveError::SendClosed)
+    );
+}
+
+#[tokio::test]
+async fn async_close_send() {
+    let (s, r) = bounded_async::<u64>(1);
+    s.send(1).await.unwrap();
+    let blocked = s.clone();
+    let h = tokio::spawn(async move { blocked.send(2).await });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(s.close_send());
+    assert_eq!(h.await.unwrap(), Err(SendError::Closed(2)));
+    assert_eq!(r.recv().await.unwrap(), 1);
+    assert_eq!(r.recv().await, Err(ReceiveError::SendClosed));
+
+    let (s, r) = bounded_async::<u64>(0);
+    let h = tokio::spawn(async move { r.recv().await });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    s.close_send();
+    assert_eq!(h.await.unwrap(), Err(ReceiveError::SendClosed));
+}
+
+#[tokio::test]
+async fn async_close_and_drain() {
+    let (s, r) = bounded_async::<u64>(1);
+    s.send(1).await.unwrap();
+    let blocked = s.clone();
+    let h = tokio::spawn(async move { blocked.send(2).await });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(r.close_and_drain(), vec![1, 2]);
+    assert_eq!(h.await.unwrap(), Ok(()));
+    assert_eq!(s.send(3).await, Err(SendError::Closed(3)));
+}
+
+#[tokio::test]
+async fn async_weak_endpoints() {
+    let (s, r) = bounded_async::<u64>(1);
+    let weak_s = s.downgrade();
+    let weak_r = r.downgrade();
+    weak_s.upgrade().unwrap().send(1).await.unwrap();
+    assert_eq!(weak_r.upgrade().unwrap().recv().await, Ok(1));
+    let h = tokio::spawn(async move { r.recv().await });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    drop(s);
+    assert_eq!(h.await.unwrap(), Err(ReceiveError::SendClosed));
+    assert!(weak_s.upgrade().is_none());
+    assert!(weak_r.upgrade().is_none());
+}
+
+#[tokio::test]
+async fn async_cancel_waiters_in_the_middle() {
+    const WAITERS: usize = 1000;
+    let (s, r) = Builder::new().bounded(0).metrics(true).build_async();
+    let mut futures: Vec<_> = (0..WAITERS).map(|_| Box::pin(r.recv())).collect();
+    for fut in futures.iter_mut() {
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+    }
+    assert_eq!(r.metrics().unwrap().waiting_receivers, WAITERS);
+    // waiters are removed from the middle of the waitlist on drop
+    let mut index = 0;
+    futures.retain(|_| {
+        index += 1;
+        index % 2 == 0
+    });
+    assert_eq!(r.metrics().unwrap().waiting_receivers, WAITERS / 2);
+    for i in 0..WAITERS / 2 {
+        s.send(i).await.unwrap();
+    }
+    assert_eq!(r.metrics().unwrap().waiting_receivers, 0);
+    for (i, fut) in futures.into_iter().enumerate() {
+        assert_eq!(fut.await.unwrap(), i);
+    }
+}
+
+#[tokio::test]
+async fn async_drop_after_handoff() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let (s, r) = new_async(Some(0));
+    let mut fut = Box::pin(r.recv());
+    assert!(futures::poll!(fut.as_mut()).is_pending());
+    s.try_send(DropTester::new(counter.clone(), 10)).unwrap();
+    // the handoff is finished by the sender, the drop only releases the received object
+    drop(fut);
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn async_timeout_cancel_races_with_peer() {
+    let (s, r) = new_async(Some(0));
+    let sender = tokio::spawn(async move {
+        let mut sent = 0;
+        for i in 0..MESSAGES / 10 {
+            if s.send_timeout(i, Duration::from_micros(10)).await.is_ok() {
+                sent += 1;
+            }
+        }
+        sent
+    });
+    let mut received = 0;
+    loop {
+        match r.recv_timeout(Duration::from_micros(10)).await {
+            Ok(_) => received += 1,
+            Err(ReceiveErrorTimeout::Timeout) => {}
+            Err(_) => break,
+        }
+    }
+    // a canceled operation reports the result of a handoff that is already finished by the peer
+    assert_eq!(sender.await.unwrap(), received);
+}
+
+#[tokio::test]
+async fn async_send_future_cancel() {
+    let (s, r) = new_async(Some(0));
+    // not polled yet
+    let mut fut = Box::pin(s.send(1));
+    assert_eq!(fut.as_mut().cancel(), Some(1));
+    // withdrawn from the waitlist
+    let mut fut = Box::pin(s.send(2));
+    assert!(futures::poll!(fut.as_mut()).is_pending());
+    assert_eq!(fut.as_mut().cancel(), Some(2));
+    assert_eq!(r.try_recv(), Err(TryRecvError::Empty));
+    // delivered to a receiver
+    let mut fut = Box::pin(s.send_owned(3));
+    assert!(futures::poll!(fut.as_mut()).is_pending());
+    assert_eq!(r.try_recv(), Ok(3));
+    assert_eq!(fut.as_mut().cancel(), None);
+    // terminated by the close of the channel
+    let mut fut = Box::pin(s.send(4));
+    assert!(futures::poll!(fut.as_mut()).is_pending());
+    drop(r);
+    assert_eq!(fut.as_mut().cancel(), Some(4));
+}
+
+#[tokio::test]
+async fn async_send_future_cancel_pinned_on_stack() {
+    let (s, r) = new_async(Some(0));
+    let mut fut = std::pin::pin!(s.send(1));
+    assert!(futures::poll!(fut.as_mut()).is_pending());
+    // the object is withdrawn from the waitlist without moving the future
+    assert_eq!(fut.as_mut().cancel(), Some(1));
+    assert_eq!(r.try_recv(), Err(TryRecvError::Empty));
+    let mut fut = std::pin::pin!(s.send_owned(2));
+    assert!(futures::poll!(fut.as_mut()).is_pending());
+    assert_eq!(r.recv().await, Ok(2));
+    assert_eq!(fut.as_mut().cancel(), None);
+}
+
+#[tokio::test]
+async fn async_poll_recv() {
+    use std::future::poll_fn;
+    let (s, r) = new_async(Some(0));
+    tokio::spawn(async move {
+        for i in 0..MESSAGES {
+            s.send(i).await.unwrap();
+        }
+    });
+    for i in 0..MESSAGES {
+        assert_eq!(poll_fn(|cx| r.poll_recv(cx)).await, Ok(i));
+    }
+    assert_eq!(
+        poll_fn(|cx| r.poll_recv(cx)).await,
+        Err(ReceiveError::SendClosed)
+    );
+}
+
+#[tokio::test]
+async fn async_poll_recv_then_recv_on_clone() {
+    use std::future::poll_fn;
+    let (s, r) = new_async(Some(0));
+    let r2 = r.clone();
+    // poll once and stop polling
+    assert!(futures::poll!(poll_fn(|cx| r.poll_recv(cx))).is_pending());
+    tokio::spawn(async move { s.send(1).await });
+    // the pending poll doesn't take the object, so the clone receives it
+    assert_eq!(r2.recv().await, Ok(1));
+    assert_eq!(
+        poll_fn(|cx| r.poll_recv(cx)).await,
+        Err(ReceiveError::SendClosed)
+    );
+}
+
+#[tokio::test]
+async fn async_poll_send() {
+    use std::future::poll_fn;
+    let (s, r) = new_async(Some(0));
+    let h = tokio::spawn(async move {
+        for i in 0..MESSAGES {
+            poll_fn(|cx| s.poll_send_ready(cx)).await.unwrap();
+            s.start_send(i).unwrap();
+        }
+        poll_fn(|cx| s.poll_flush(cx)).await.unwrap();
+    });
+    for i in 0..MESSAGES {
+        assert_eq!(r.recv().await, Ok(i));
+    }
+    h.await.unwrap();
+    assert_eq!(r.recv().await, Err(ReceiveError::SendClosed));
+}
+
+#[tokio::test]
+async fn async_poll_send_closed() {
+    use std::future::poll_fn;
+    let counter = Arc::new(AtomicUsize::new(0));
+    let (s, r) = new_async(Some(0));
+    // the object waits in the waitlist until the receiver gets closed
+    s.start_send(DropTester::new(counter.clone(), 1)).unwrap();
+    assert!(futures::poll!(poll_fn(|cx| s.poll_send_ready(cx))).is_pending());
+    drop(r);
+    assert!(matches!(
+        poll_fn(|cx| s.poll_send_ready(cx)).await,
+        Err(SendError::Closed(Some(_)))
+    ));
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+    assert!(matches!(
+        s.start_send(DropTester::new(counter.clone(), 2)),
+        Err(TrySendError::ReceiveClosed(_))
+    ));
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+    assert!(matches!(
+        poll_fn(|cx| s.poll_send_ready(cx)).await,
+        Err(SendError::ReceiveClosed(None))
+    ));
+    // the waiting object is dropped with the sender
+    let (s, _r) = new_async(Some(0));
+    s.start_send(DropTester::new(counter.clone(), 3)).unwrap();
+    drop(s);
+    assert_eq!(counter.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn async_poll_send_ready_room() {
+    use std::future::poll_fn;
+    let (s, r) = new_async(Some(1));
+    s.send(1).await.unwrap();
+    // the channel is full, so the sender waits for room
+    assert!(futures::poll!(poll_fn(|cx| s.poll_send_ready(cx))).is_pending());
+    assert_eq!(r.recv().await, Ok(1));
+    assert_eq!(poll_fn(|cx| s.poll_send_ready(cx)).await, Ok(()));
+    // the object of a bounded(0) channel stays in flight until a receiver takes it
+    let (s, r) = new_async(Some(0));
+    s.start_send(1).unwrap();
+    assert_eq!(s.start_send(2), Err(TrySendError::Full(2)));
+    assert_eq!(r.recv().await, Ok(1));
+    assert!(futures::poll!(poll_fn(|cx| s.poll_send_ready(cx))).is_pending());
+    r.close();
+    assert_eq!(
+        poll_fn(|cx| s.poll_send_ready(cx)).await,
+        Err(SendError::Closed(None))
+    );
+}
+
+#[tokio::test]
+async fn async_poll_shared_handles() {
+    use std::future::poll_fn;
+    let (s, r) = new_async(Some(0));
+    let (s, r) = (Arc::new(s), Arc::new(r));
+    // every task that polls the shared receiver is woken, none of them displaces another
+    let receivers: Vec<_> = (0..4)
+        .map(|_| {
+            let r = r.clone();
+            tokio::spawn(async move { poll_fn(|cx| r.poll_recv(cx)).await })
+        })
+        .collect();
+    tokio::task::yield_now().await;
+    let senders: Vec<_> = (0..4)
+        .map(|i| {
+            let s = s.clone();
+            tokio::spawn(async move {
+                loop {
+                    poll_fn(|cx| s.poll_send_ready(cx)).await.unwrap();
+                    match s.start_send(i) {
+                        Ok(()) => break,
+                        Err(TrySendError::Full(_)) => continue,
+                        Err(err) => panic!("{:?}", err),
+                    }
+                }
+                poll_fn(|cx| s.poll_flush(cx)).await.unwrap();
+            })
+        })
+        .collect();
+    let mut received = Vec::new();
+    for h in receivers {
+        received.push(h.await.unwrap().unwrap());
+    }
+    for h in senders {
+        h.await.unwrap();
+    }
+    received.sort();
+    assert_eq!(received, vec![0, 1, 2, 3]);
+}
+
 async fn async_two_msg(size: usize) {
     let (s, r) = bounded_async::<u8>(size);
     tokio::spawn(async move {
@@ -1,7 +1,13 @@
+#![allow(dead_code, clippy::unnecessary_cast)]
 mod common;
 
 use common::*;
-use kanal::{bounded, unbounded, ReceiveError, Receiver, SendError, Sender};
+use kanal::{
+    bounded, bounded_with_overflow, broadcast, oneshot, priority_bounded, priority_unbounded,
+    unbounded, watch, BroadcastReceiveError, BroadcastTryRecvError, Builder, OverflowPolicy,
+    PriorityOrder, ReceiveError, ReceiveErrorTimeout, Receiver, SendError, SendErrorTimeout,
+    SendManyError, Sender, TryRecvError, TrySendError,
+};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -239,14 +245,98 @@ fn recv_from_closed_channel_queue() {
 fn send_to_half_closed_channel() {
     let (tx, rx) = new(Some(1));
     drop(rx);
-    assert_eq!(tx.send(1).err().unwrap(), SendError::ReceiveClosed);
+    assert_eq!(tx.send(1).err().unwrap(), SendError::ReceiveClosed(1));
 }
 
 #[test]
 fn send_to_closed_channel() {
     let (tx, rx) = new(Some(1));
     rx.close();
-    assert_eq!(tx.send(1).err().unwrap(), SendError::Closed);
+    assert_eq!(tx.send(1).err().unwrap(), SendError::Closed(1));
+}
+
+#[test]
+fn try_send_to_full_channel() {
+    let (tx, rx) = new(Some(1));
+    tx.try_send(1).unwrap();
+    assert_eq!(tx.try_send(2).err().unwrap(), TrySendError::Full(2));
+    assert_eq!(rx.recv().unwrap(), 1);
+    drop(rx);
+    assert_eq!(
+        tx.try_send(3).err().unwrap(),
+        TrySendError::ReceiveClosed(3)
+    );
+}
+
+#[test]
+fn try_recv_from_empty_channel() {
+    let (tx, rx) = new(Some(1));
+    assert_eq!(rx.try_recv().err().unwrap(), TryRecvError::Empty);
+    tx.send(1).unwrap();
+    assert_eq!(rx.try_recv().unwrap(), 1);
+    drop(tx);
+    assert_eq!(rx.try_recv().err().unwrap(), TryRecvError::SendClosed);
+    rx.close();
+    assert_eq!(rx.try_recv().err().unwrap(), TryRecvError::Closed);
+}
+
+#[test]
+fn error_predicates() {
+    assert!(TrySendError::Full(1).is_full());
+    assert!(!TrySendError::Full(1).is_disconnected());
+    assert!(TrySendError::Contended(1).is_contended());
+    assert!(TrySendError::ReceiveClosed(1).is_disconnected());
+    assert!(!TrySendError::ReceiveClosed(1).is_closed());
+    assert!(SendError::Closed(1).is_closed());
+    assert!(SendErrorTimeout::Timeout(1).is_timeout());
+    assert!(!SendErrorTimeout::Timeout(1).is_disconnected());
+    assert!(TryRecvError::Empty.is_empty());
+    assert!(TryRecvError::SendClosed.is_disconnected());
+    assert!(ReceiveError::SendClosed.is_disconnected());
+    assert!(ReceiveErrorTimeout::Timeout.is_timeout());
+    assert!(!ReceiveErrorTimeout::Timeout.is_disconnected());
+}
+
+#[test]
+fn send_timeout_returns_data() {
+    let (tx, _rx) = new(Some(0));
+    let err = tx.send_timeout(1, Duration::from_millis(10)).err().unwrap();
+    assert_eq!(err, SendErrorTimeout::Timeout(1));
+    assert_eq!(err.into_inner(), 1);
+}
+
+#[test]
+fn send_returns_data_on_close() {
+    let (tx, rx) = new(Some(0));
+    crossbeam::scope(|scope| {
+        let t = scope.spawn(|_| tx.send(String::from("not delivered")));
+        std::thread::sleep(Duration::from_millis(100));
+        rx.close();
+        let err = t.join().unwrap().err().unwrap();
+        assert_eq!(err.into_inner(), "not delivered");
+    })
+    .unwrap();
+}
+
+#[test]
+#[allow(deprecated)]
+fn send_option_keeps_data_on_failure() {
+    let (s, r) = new(Some(1));
+    let mut opt = Some(1);
+    assert_eq!(s.try_send_option(&mut opt), Ok(true));
+    assert_eq!(opt, None);
+    let mut opt = Some(2);
+    assert_eq!(s.try_send_option(&mut opt), Ok(false));
+    assert_eq!(s.try_send_option_realtime(&mut opt), Ok(false));
+    assert_eq!(
+        s.send_option_timeout(&mut opt, Duration::from_millis(10)),
+        Err(SendErrorTimeout::Timeout(()))
+    );
+    assert_eq!(opt, Some(2));
+    assert_eq!(r.recv().unwrap(), 1);
+    r.close();
+    assert_eq!(s.try_send_option(&mut opt), Err(SendError::Closed(())));
+    assert_eq!(opt, Some(2));
 }
 
 // Channel drop tests
@@ -280,6 +370,24 @@ fn drop_test_send_to_closed() {
     assert_eq!(counter.load(Ordering::SeqCst), 10_usize);
 }
 
+#[test]
+fn drop_test_returned_send_error() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let (s, r) = new(Some(10));
+    drop(r);
+    for i in 1..=10 {
+        // the object is handed back and dropped by the caller exactly once
+        let data = s
+            .send(DropTester::new(counter.clone(), i))
+            .err()
+            .unwrap()
+            .into_inner();
+        assert_eq!(data.i, i);
+        assert_eq!(counter.load(Ordering::SeqCst), i - 1);
+    }
+    assert_eq!(counter.load(Ordering::SeqCst), 10_usize);
+}
+
 #[test]
 fn drop_test_send_to_half_closed() {
     let counter = Arc::new(AtomicUsize::new(0));
@@ -317,6 +425,966 @@ fn drop_test_in_signal() {
     .unwrap();
 }
 
+#[test]
+fn oneshot_send_recv() {
+    crossbeam::scope(|scope| {
+        for _ in 0..MESSAGES / 100 {
+            let (s, r) = oneshot();
+            let (s2, r2) = oneshot();
+            scope.spawn(move |_| {
+                s.send(1u8).unwrap();
+                s2.send((0u64, !0u64, 0u64, !0u64)).unwrap();
+            });
+            assert_eq!(r.recv().unwrap(), 1);
+            assert_eq!(r2.recv().unwrap(), (0u64, !0u64, 0u64, !0u64));
+        }
+    })
+    .unwrap();
+}
+
+#[test]
+fn oneshot_closed() {
+    let (s, r) = oneshot::<u64>();
+    drop(s);
+    assert_eq!(r.recv(), Err(ReceiveError::SendClosed));
+    let (s, r) = oneshot();
+    drop(r);
+    assert_eq!(
+        s.send(String::from("back")).err().unwrap().into_inner(),
+        "back"
+    );
+    // the sender is dropped while the receiver is waiting
+    let (s, r) = oneshot::<u64>();
+    crossbeam::scope(|scope| {
+        scope.spawn(move |_| {
+            std::thread::sleep(Duration::from_millis(100));
+            drop(s);
+        });
+        assert_eq!(r.recv(), Err(ReceiveError::SendClosed));
+    })
+    .unwrap();
+}
+
+#[test]
+fn oneshot_recv_timeout() {
+    let (s, mut r) = oneshot();
+    assert_eq!(
+        r.recv_timeout(Duration::from_millis(10)),
+        Err(ReceiveErrorTimeout::Timeout)
+    );
+    crossbeam::scope(|scope| {
+        scope.spawn(move |_| {
+            std::thread::sleep(Duration::from_millis(100));
+            s.send(String::from("late")).unwrap();
+        });
+        assert_eq!(r.recv_timeout(Duration::from_secs(10)).unwrap(), "late");
+    })
+    .unwrap();
+    assert_eq!(r.try_recv(), Err(TryRecvError::Closed));
+}
+
+#[test]
+fn oneshot_drop_test() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let (s, r) = oneshot();
+    s.send(DropTester::new(counter.clone(), 10)).unwrap();
+    assert_eq!(counter.load(Ordering::SeqCst), 0_usize);
+    drop(r);
+    assert_eq!(counter.load(Ordering::SeqCst), 1_usize);
+    let (s, r) = oneshot();
+    drop(r);
+    drop(s.send(DropTester::new(counter.clone(), 10)));
+    assert_eq!(counter.load(Ordering::SeqCst), 2_usize);
+}
+
+#[test]
+fn broadcast_every_receiver() {
+    let (s, r) = broadcast(MESSAGES);
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let mut r = r.clone();
+            std::thread::spawn(move || {
+                for i in 0..MESSAGES {
+                    assert_eq!(r.recv().unwrap(), i);
+                }
+                assert_eq!(r.recv(), Err(BroadcastReceiveError::SendClosed));
+            })
+        })
+        .collect();
+    drop(r);
+    for i in 0..MESSAGES {
+        s.send(i).unwrap();
+    }
+    drop(s);
+    for h in handles {
+        h.join().unwrap();
+    }
+}
+
+#[test]
+fn broadcast_lagged() {
+    let (s, mut r) = broadcast(4);
+    let mut fast = r.clone();
+    for i in 0..10 {
+        s.send(i).unwrap();
+        assert_eq!(fast.try_recv().unwrap(), i);
+    }
+    assert_eq!(r.len(), 4);
+    assert_eq!(r.recv(), Err(BroadcastReceiveError::Lagged(6)));
+    for i in 6..10 {
+        assert_eq!(r.recv().unwrap(), i);
+    }
+    assert_eq!(r.try_recv(), Err(BroadcastTryRecvError::Empty));
+    drop(s);
+    assert!(r.is_terminated());
+    assert_eq!(r.try_recv(), Err(BroadcastTryRecvError::SendClosed));
+}
+
+#[test]
+fn broadcast_drop_test() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let (s, r) = broadcast(2);
+    for i in 1..=5 {
+        s.send(Arc::new(DropTester::new(counter.clone(), i)))
+            .unwrap();
+    }
+    // overwritten objects are dropped by the sender
+    assert_eq!(counter.load(Ordering::SeqCst), 3_usize);
+    drop(r);
+    assert_eq!(counter.load(Ordering::SeqCst), 5_usize);
+    let err = s
+        .send(Arc::new(DropTester::new(counter.clone(), 10)))
+        .unwrap_err();
+    assert!(err.is_disconnected());
+    drop(err);
+    assert_eq!(counter.load(Ordering::SeqCst), 6_usize);
+}
+
+#[test]
+fn watch_every_change_observed() {
+    let (s, r) = watch(0);
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let mut r = r.clone();
+            std::thread::spawn(move || {
+                let mut last = 0;
+                while r.wait_changed().is_ok() {
+                    let v = *r.borrow_and_update();
+                    assert!(v > last);
+                    last = v;
+                }
+                assert_eq!(last, MESSAGES);
+            })
+        })
+        .collect();
+    drop(r);
+    for i in 1..=MESSAGES {
+        s.send(i).unwrap();
+    }
+    drop(s);
+    for h in handles {
+        h.join().unwrap();
+    }
+}
+
+#[test]
+fn watch_borrow_does_not_block_send() {
+    let (s, r) = watch(0);
+    let value = r.borrow();
+    s.send(1).unwrap();
+    assert_eq!(*value, 0);
+    assert_eq!(*r.borrow(), 1);
+}
+
+#[test]
+fn watch_wait_changed_timeout() {
+    let (s, mut r) = watch(0);
+    assert_eq!(
+        r.wait_changed_timeout(Duration::from_millis(10)),
+        Err(ReceiveErrorTimeout::Timeout)
+    );
+    let t = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(10));
+        s.send(1).unwrap();
+    });
+    r.wait_changed_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(*r.borrow(), 1);
+    t.join().unwrap();
+    assert!(r.is_terminated());
+    assert_eq!(
+        r.wait_changed_timeout(Duration::from_millis(10)),
+        Err(ReceiveErrorTimeout::SendClosed)
+    );
+    assert_eq!(r.wait_changed(), Err(ReceiveError::SendClosed));
+}
+
+#[test]
+fn priority_order() {
+    let (s, r) = priority_unbounded();
+    for i in 0..MESSAGES {
+        s.send(i % 100).unwrap();
+    }
+    let mut last = usize::MAX;
+    for _ in 0..MESSAGES {
+        let v = r.recv().unwrap();
+        assert!(v <= last);
+        last = v;
+    }
+    assert!(r.is_empty());
+}
+
+#[test]
+fn priority_stable_with_aging() {
+    let (s, r) = PriorityOrder::by(|a: &(u8, usize), b: &(u8, usize)| a.0.cmp(&b.0))
+        .aging(3)
+        .unbounded();
+    s.send((0, 0)).unwrap();
+    for i in 1..=6 {
+        s.send((1, i)).unwrap();
+    }
+    // equal priorities keep the send order, and the low-priority object is received after 3 receives
+    let received: Vec<_> = (0..7).map(|_| r.recv().unwrap().1).collect();
+    assert_eq!(received, [1, 2, 3, 0, 4, 5, 6]);
+}
+
+#[test]
+fn priority_mpmc() {
+    let (s, r) = priority_bounded(10);
+    let sum = Arc::new(AtomicUsize::new(0));
+    let mut handles = Vec::new();
+    for t in 0..THREADS {
+        let s = s.clone();
+        handles.push(std::thread::spawn(move || {
+            for i in 0..MESSAGES / THREADS {
+                s.send(t * MESSAGES + i).unwrap();
+            }
+        }));
+        let r = r.clone();
+        let sum = sum.clone();
+        handles.push(std::thread::spawn(move || {
+            for v in r {
+                sum.fetch_add(v % MESSAGES, Ordering::SeqCst);
+            }
+        }));
+    }
+    drop((s, r));
+    for h in handles {
+        h.join().unwrap();
+    }
+    let per_thread = MESSAGES / THREADS;
+    assert_eq!(
+        sum.load(Ordering::SeqCst),
+        THREADS * per_thread * (per_thread - 1) / 2
+    );
+}
+
+#[test]
+fn priority_direct_handoff() {
+    let (s, r) = priority_bounded(0);
+    let t = std::thread::spawn(move || r.recv().unwrap());
+    std::thread::sleep(Duration::from_millis(10));
+    s.send(1).unwrap();
+    assert_eq!(t.join().unwrap(), 1);
+}
+
+#[test]
+fn overflow_drop_oldest() {
+    let (s, r) = bounded_with_overflow(10, OverflowPolicy::DropOldest);
+    // senders never wait even without an active receiver
+    for i in 0..MESSAGES {
+        s.send(i).unwrap();
+    }
+    assert_eq!(s.evicted_count(), (MESSAGES - 10) as u64);
+    for i in MESSAGES - 10..MESSAGES {
+        assert_eq!(r.recv().unwrap(), i);
+    }
+    assert_eq!(s.try_send_evicting(0).unwrap(), None);
+}
+
+#[test]
+fn overflow_drop_newest() {
+    let (s, r) = bounded_with_overflow(2, OverflowPolicy::DropNewest);
+    s.try_send(1).unwrap();
+    s.send_timeout(2, Duration::from_millis(10)).unwrap();
+    assert_eq!(s.try_send_evicting(3).unwrap(), Some(3));
+    s.send(4).unwrap();
+    assert_eq!(r.evicted_count(), 2);
+    assert_eq!(r.recv().unwrap(), 1);
+    assert_eq!(r.recv().unwrap(), 2);
+    assert!(r.is_empty());
+    let (s, _r) = bounded_with_overflow(1, OverflowPolicy::Block);
+    s.send(1).unwrap();
+    assert_eq!(s.try_send_evicting(2), Err(TrySendError::Full(2)));
+}
+
+#[test]
+fn overflow_drop_test() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let (s, r) = bounded_with_overflow(1, OverflowPolicy::DropOldest);
+    for i in 1..=5 {
+        s.send(DropTester::new(counter.clone(), i)).unwrap();
+    }
+    assert_eq!(counter.load(Ordering::SeqCst), 4_usize);
+    let evicted = s
+        .try_send_evicting(DropTester::new(counter.clone(), 10))
+        .unwrap()
+        .unwrap();
+    assert_eq!(evicted.i, 5);
+    drop(evicted);
+    assert_eq!(counter.load(Ordering::SeqCst), 5_usize);
+    drop((s, r));
+    assert_eq!(counter.load(Ordering::SeqCst), 6_usize);
+}
+
+#[test]
+#[should_panic(expected = "overflow policy of a zero sized channel must be Block")]
+fn overflow_zero_sized() {
+    let _ = bounded_with_overflow::<u64>(0, OverflowPolicy::DropNewest);
+}
+
+#[test]
+fn priority_overflow_drop_oldest() {
+    let (s, r) = Builder::new()
+        .bounded(3)
+        .overflow(OverflowPolicy::DropOldest)
+        .priority(PriorityOrder::natural().aging(1))
+        .build();
+    for v in 0..10 {
+        s.send(v).unwrap();
+    }
+    assert_eq!(s.evicted_count(), 7);
+    assert_eq!(r.recv().unwrap(), 9);
+    s.send(1).unwrap();
+    // the oldest object is received after aging regardless of its priority
+    assert_eq!(r.recv().unwrap(), 7);
+    s.send(2).unwrap();
+    s.send(3).unwrap();
+    // the queue is full again, and the oldest object is evicted even though it has the highest priority
+    assert_eq!(s.evicted_count(), 8);
+    assert_eq!(r.recv().unwrap(), 1);
+    assert_eq!(r.recv().unwrap(), 2);
+    assert_eq!(r.recv().unwrap(), 3);
+}
+
+#[test]
+fn builder_options() {
+    let (s, r) = Builder::new()
+        .bounded(2)
+        .overflow(OverflowPolicy::DropOldest)
+        .priority(PriorityOrder::natural())
+        .name("jobs")
+        .preallocate(1)
+        .metrics(true)
+        .build();
+    for v in [3, 1, 2] {
+        s.send(v).unwrap();
+    }
+    // the oldest object is evicted, not the lowest priority one
+    assert_eq!(r.recv().unwrap(), 2);
+    assert_eq!(r.recv().unwrap(), 1);
+    let metrics = r.metrics().unwrap();
+    assert_eq!(metrics.sent, 3);
+    assert_eq!(metrics.evicted, 1);
+    assert_eq!(metrics.peak_len, 2);
+    assert_eq!(metrics.len, 0);
+    assert_eq!(format!("{:?}", r), r#"Receiver { name: "jobs", .. }"#);
+}
+
+#[test]
+fn builder_without_spins() {
+    let (s, r) = Builder::new().bounded(0).spins(0).metrics(true).build();
+    let h = std::thread::spawn(move || {
+        for i in 0..MESSAGES {
+            s.send(i).unwrap();
+        }
+    });
+    for i in 0..MESSAGES {
+        assert_eq!(r.recv().unwrap(), i);
+    }
+    h.join().unwrap();
+    assert_eq!(r.metrics().unwrap().sent, MESSAGES as u64);
+}
+
+#[test]
+fn select_recv_every_message() {
+    let (s1, r1) = bounded::<usize>(0);
+    let (s2, r2) = new::<usize>(Some(10));
+    // the senders are kept alive, so the receive operations don't complete with errors
+    let (s1c, s2c) = (s1.clone(), s2.clone());
+    let h1 = std::thread::spawn(move || {
+        for i in 0..MESSAGES {
+            s1c.send(i).unwrap();
+        }
+    });
+    let h2 = std::thread::spawn(move || {
+        for i in 0..MESSAGES {
+            s2c.send(i).unwrap();
+        }
+    });
+    let (mut next1, mut next2) = (0, 0);
+    for _ in 0..MESSAGES * 2 {
+        kanal::select! {
+            recv(r1) -> v => {
+                assert_eq!(v.unwrap(), next1);
+                next1 += 1;
+            }
+            recv(r2) -> v => {
+                assert_eq!(v.unwrap(), next2);
+                next2 += 1;
+            }
+        }
+    }
+    assert_eq!((next1, next2), (MESSAGES, MESSAGES));
+    h1.join().unwrap();
+    h2.join().unwrap();
+    drop((s1, s2));
+}
+
+#[test]
+fn select_mpmc_no_lost_messages() {
+    let (s1, r1) = bounded::<usize>(0);
+    let (s2, r2) = bounded::<usize>(1);
+    let received = AtomicUsize::new(0);
+    crossbeam::scope(|scope| {
+        for _ in 0..THREADS {
+            let (s1, s2) = (s1.clone(), s2.clone());
+            scope.spawn(move |_| {
+                for i in 0..MESSAGES / THREADS {
+                    if i % 2 == 0 {
+                        s1.send(1).unwrap();
+                    } else {
+                        s2.send(1).unwrap();
+                    }
+                }
+            });
+        }
+        drop((s1, s2));
+        for _ in 0..THREADS / 2 {
+            scope.spawn(|_| {
+                let (mut closed1, mut closed2) = (false, false);
+                while !closed1 || !closed2 {
+                    kanal::select! {
+                        recv(r1) -> v => match v {
+                            Ok(v) => received.fetch_add(v, Ordering::SeqCst),
+                            Err(_) => {
+                                closed1 = true;
+                                0
+                            }
+                        },
+                        recv(r2) -> v => match v {
+                            Ok(v) => received.fetch_add(v, Ordering::SeqCst),
+                            Err(_) => {
+                                closed2 = true;
+                                0
+                            }
+                        },
+                    };
+                }
+            });
+            scope.spawn(|_| {
+                while let Ok(v) = r1.recv() {
+                    received.fetch_add(v, Ordering::SeqCst);
+                }
+            });
+        }
+    })
+    .unwrap();
+    assert_eq!(
+        received.load(Ordering::SeqCst),
+        MESSAGES / THREADS * THREADS
+    );
+}
+
+#[test]
+fn select_send() {
+    let (s1, r1) = bounded::<usize>(0);
+    let (s2, _r2) = bounded::<usize>(0);
+    let h = std::thread::spawn(move || {
+        for i in 0..MESSAGES {
+            assert_eq!(r1.recv().unwrap(), i);
+        }
+    });
+    for i in 0..MESSAGES {
+        kanal::select! {
+            send(s1, i) -> res => res.unwrap(),
+            send(s2, i) -> _ => panic!("nobody receives from the channel"),
+        }
+    }
+    h.join().unwrap();
+}
+
+#[test]
+fn select_default_and_timeout() {
+    let (s, r) = bounded::<u64>(0);
+    let selected = kanal::select! {
+        recv(r) -> _ => false,
+        send(s, 1) -> _ => false,
+        default => true,
+    };
+    assert!(selected);
+    let now = std::time::Instant::now();
+    let selected = kanal::select! {
+        recv(r) -> _ => false,
+        timeout(Duration::from_millis(50)) => true,
+    };
+    assert!(selected);
+    assert!(now.elapsed() >= Duration::from_millis(50));
+    assert_eq!(r.receiver_count(), 1);
+    assert_eq!(s.try_send(1), Err(TrySendError::Full(1)));
+}
+
+#[test]
+fn select_closed() {
+    let (s1, r1) = bounded::<u64>(0);
+    let (s2, r2) = bounded::<u64>(0);
+    drop(s1);
+    kanal::select! {
+        recv(r1) -> v => assert_eq!(v, Err(ReceiveError::SendClosed)),
+        recv(r2) -> _ => unreachable!(),
+    }
+    drop(r2);
+    kanal::select! {
+        send(s2, 5) -> v => assert_eq!(v, Err(SendError::ReceiveClosed(5))),
+    }
+}
+
+#[test]
+fn select_builder() {
+    let (s1, r1) = bounded::<u64>(1);
+    let (s2, r2) = unbounded::<String>();
+    s2.send("hello".to_string()).unwrap();
+    let mut select = kanal::Select::new();
+    let first = select.send(&s1, 1);
+    let second = select.recv(&r2);
+    let op = select.select();
+    if op.index() == first {
+        op.send(&s1).unwrap();
+        assert_eq!(r1.recv().unwrap(), 1);
+    } else {
+        assert_eq!(op.index(), second);
+        assert_eq!(op.recv(&r2).unwrap(), "hello");
+    }
+    let mut select = kanal::Select::new();
+    select.recv(&r1);
+    assert!(select.select_timeout(Duration::from_millis(10)).is_none());
+}
+
+#[test]
+fn select_drop_test() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let (s1, r1) = bounded::<DropTester>(0);
+    let (s2, r2) = bounded::<DropTester>(1);
+    kanal::select! {
+        send(s1, DropTester::new(counter.clone(), 1)) -> _ => unreachable!(),
+        send(s2, DropTester::new(counter.clone(), 2)) -> res => res.unwrap(),
+    }
+    assert_eq!(counter.load(Ordering::SeqCst), 1_usize);
+    drop(r2.recv().unwrap());
+    assert_eq!(counter.load(Ordering::SeqCst), 2_usize);
+    drop((s1, r1));
+}
+
+#[test]
+fn send_many_seq() {
+    for cap in [Some(0), Some(1), Some(100), None] {
+        let (s, r) = new(cap);
+        let h = std::thread::spawn(move || {
+            for i in 0..MESSAGES {
+                assert_eq!(r.recv().unwrap(), i);
+            }
+        });
+        assert_eq!(s.send_many(0..MESSAGES).unwrap(), MESSAGES);
+        h.join().unwrap();
+    }
+}
+
+#[test]
+fn send_many_to_waiting_receivers() {
+    let (s, r) = Builder::new().bounded(0).metrics(true).build();
+    let received = Arc::new(AtomicUsize::new(0));
+    let mut list = Vec::new();
+    for _ in 0..THREADS {
+        let r = r.clone();
+        let received = received.clone();
+        list.push(std::thread::spawn(move || {
+            received.fetch_add(r.recv().unwrap(), Ordering::SeqCst);
+        }));
+    }
+    while s.metrics().unwrap().waiting_receivers < THREADS {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    let mut items = vec![1; THREADS * 2].into_iter();
+    assert_eq!(s.try_send_many(&mut items).unwrap(), THREADS);
+    assert_eq!(items.len(), THREADS);
+    for h in list {
+        h.join().unwrap();
+    }
+    assert_eq!(received.load(Ordering::SeqCst), THREADS);
+    assert_eq!(s.metrics().unwrap().sent, THREADS as u64);
+}
+
+#[test]
+fn send_many_closed() {
+    let (s, r) = bounded::<u64>(2);
+    let h = std::thread::spawn(move || {
+        assert_eq!(r.recv().unwrap(), 0);
+        std::thread::sleep(Duration::from_millis(10));
+        drop(r);
+    });
+    // the blocked send of the fourth object is terminated by the drop of the receiver
+    let mut items = 0..10;
+    assert_eq!(
+        s.send_many(&mut items),
+        Err(SendManyError::Closed(3, Some(3)))
+    );
+    assert_eq!(items.next(), Some(4));
+    h.join().unwrap();
+    assert_eq!(
+        s.try_send_many(0..10),
+        Err(SendManyError::ReceiveClosed(0, None))
+    );
+}
+
+#[test]
+fn send_many_extend_closed() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let (mut s, r) = unbounded();
+    drop(r);
+    s.extend((1..=3).map(|i| DropTester::new(counter.clone(), i)));
+    assert_eq!(counter.load(Ordering::SeqCst), 1_usize);
+}
+
+#[test]
+#[should_panic(expected = "extend is only supported on unbounded channels")]
+fn send_many_extend_bounded() {
+    let (mut s, _r) = bounded(10);
+    s.extend([1, 2, 3]);
+}
+
+#[test]
+fn send_many_extend() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let (mut s, r) = unbounded();
+    s.extend((1..=10).map(|i| DropTester::new(counter.clone(), i)));
+    assert_eq!(r.len(), 10);
+    let (s2, r2) = bounded_with_overflow(2, OverflowPolicy::DropOldest);
+    assert_eq!(
+        s2.try_send_many(std::iter::from_fn(|| r.try_recv().ok()))
+            .unwrap(),
+        10
+    );
+    assert_eq!(counter.load(Ordering::SeqCst), 8_usize);
+    assert_eq!(r2.recv().unwrap().i, 9);
+    drop((s, s2, r2));
+    assert_eq!(counter.load(Ordering::SeqCst), 10_usize);
+}
+
+#[test]
+fn recv_many_seq() {
+    for cap in [Some(0), Some(1), Some(100), None] {
+        let (s, r) = new(cap);
+        let h = std::thread::spawn(move || {
+            for i in 0..MESSAGES {
+                s.send(i).unwrap();
+            }
+        });
+        let mut buf = Vec::new();
+        while buf.len() < MESSAGES {
+            let before = buf.len();
+            let count = r.recv_many(&mut buf, 64).unwrap();
+            assert!(count > 0 && count <= 64);
+            assert_eq!(buf.len(), before + count);
+        }
+        assert!(buf.iter().copied().eq(0..MESSAGES));
+        h.join().unwrap();
+        assert_eq!(r.recv_many(&mut buf, 64), Err(ReceiveError::SendClosed));
+    }
+}
+
+#[test]
+fn recv_many_from_waiting_senders() {
+    let (s, r) = Builder::new().bounded(2).metrics(true).build();
+    let mut list = Vec::new();
+    for i in 0..THREADS {
+        let s = s.clone();
+        list.push(std::thread::spawn(move || s.send(i).unwrap()));
+    }
+    while s.len() + s.metrics().unwrap().waiting_senders < THREADS {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    let mut buf = Vec::new();
+    assert_eq!(r.try_recv_many(&mut buf, THREADS - 1).unwrap(), THREADS - 1);
+    // blocked senders moved their objects to the queue
+    assert_eq!(r.len(), 1);
+    for h in list {
+        h.join().unwrap();
+    }
+    assert_eq!(r.try_recv_many(&mut buf, THREADS).unwrap(), 1);
+    buf.sort();
+    assert_eq!(buf, (0..THREADS).collect::<Vec<_>>());
+    assert_eq!(r.try_recv_many(&mut buf, 0), Ok(0));
+    assert_eq!(r.try_recv_many(&mut buf, 1), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn recv_many_drains_after_wake_up() {
+    let (s, r) = Builder::new().unbounded().metrics(true).build();
+    let h = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        assert_eq!(r.recv_many(&mut buf, 10).unwrap(), 5);
+        buf
+    });
+    while s.metrics().unwrap().waiting_receivers == 0 {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    // the waiting receiver takes no object, so the whole batch is buffered and drained at once
+    assert_eq!(s.try_send_many(1..=5).unwrap(), 5);
+    assert_eq!(h.join().unwrap(), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn recv_batch_timeout() {
+    let (s, r) = bounded::<u64>(0);
+    let h = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(20));
+        s.send(1).unwrap();
+        s.send(2).unwrap();
+        std::thread::sleep(Duration::from_millis(300));
+        s.send(3).unwrap();
+    });
+    // the window starts from the first object, so waiting for it doesn't cut the batch
+    assert_eq!(
+        r.recv_batch_timeout(10, Duration::from_millis(100))
+            .unwrap(),
+        vec![1, 2]
+    );
+    assert_eq!(
+        r.recv_batch_timeout(10, Duration::from_millis(10)).unwrap(),
+        vec![3]
+    );
+    h.join().unwrap();
+    assert_eq!(
+        r.recv_batch_timeout(10, Duration::from_millis(10)),
+        Err(ReceiveError::SendClosed)
+    );
+
+    let (s, r) = unbounded();
+    s.send_many(0..100).unwrap();
+    // a full batch is returned without waiting for the window
+    let now = std::time::Instant::now();
+    let batch = r.recv_batch_timeout(60, Duration::from_secs(10)).unwrap();
+    assert!(batch.iter().copied().eq(0..60));
+    drop(s);
+    assert_eq!(
+        r.recv_batch_timeout(60, Duration::from_secs(10))
+            .unwrap()
+            .len(),
+        40
+    );
+    assert!(now.elapsed() < Duration::from_secs(5));
+    assert_eq!(r.recv_batch_timeout(0, Duration::ZERO), Ok(vec![]));
+}
+
+#[test]
+fn close_send_drains_queue() {
+    let (s, r) = Builder::new().bounded(2).metrics(true).build();
+    s.send(1).unwrap();
+    s.send(2).unwrap();
+    let blocked = s.clone();
+    let h = std::thread::spawn(move || blocked.send(3));
+    while s.metrics().unwrap().waiting_senders == 0 {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert!(s.close_send());
+    assert!(!s.close_send());
+    // the waiting sender is released with its object
+    assert_eq!(h.join().unwrap(), Err(SendError::Closed(3)));
+    assert_eq!(s.try_send(4), Err(TrySendError::Closed(4)));
+    assert!(r.is_disconnected());
+    assert!(!r.is_terminated());
+    assert_eq!(r.recv().unwrap(), 1);
+    assert_eq!(r.recv().unwrap(), 2);
+    assert_eq!(r.recv(), Err(ReceiveError::SendClosed));
+    assert!(r.is_terminated());
+}
+
+#[test]
+fn close_send_wakes_receivers() {
+    let (s, r) = bounded::<u64>(0);
+    let h = std::thread::spawn(move || r.recv());
+    std::thread::sleep(Duration::from_millis(10));
+    let other = s.clone();
+    s.close_send();
+    assert_eq!(h.join().unwrap(), Err(ReceiveError::SendClosed));
+    assert_eq!(other.send(1), Err(SendError::Closed(1)));
+    assert_eq!(other.sender_count(), 0);
+}
+
+#[test]
+fn close_and_drain() {
+    let (s, r) = Builder::new().bounded(2).metrics(true).build();
+    s.send(1).unwrap();
+    s.send(2).unwrap();
+    let blocked = s.clone();
+    let h = std::thread::spawn(move || blocked.send(3));
+    while s.metrics().unwrap().waiting_senders == 0 {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(r.close_and_drain(), vec![1, 2, 3]);
+    // the waiting sender completes as its object is handed back to the caller
+    assert_eq!(h.join().unwrap(), Ok(()));
+    assert!(s.is_closed());
+    assert!(r.close_and_drain().is_empty());
+    assert_eq!(r.recv(), Err(ReceiveError::Closed));
+}
+
+#[test]
+fn close_and_drain_priority() {
+    let (s, r) = PriorityOrder::natural().unbounded();
+    for i in [2, 5, 1, 4] {
+        s.send(i).unwrap();
+    }
+    assert_eq!(s.close_and_drain(), vec![5, 4, 2, 1]);
+    assert!(r.is_closed());
+}
+
+#[test]
+fn close_and_drain_wakes_receivers() {
+    let (s, r) = bounded::<u64>(0);
+    let h = std::thread::spawn(move || r.recv());
+    std::thread::sleep(Duration::from_millis(10));
+    assert!(s.close_and_drain().is_empty());
+    assert_eq!(h.join().unwrap(), Err(ReceiveError::Closed));
+}
+
+#[test]
+fn close_drops_outside_of_lock() {
+    // dropping a buffered sender acquires the lock of its own channel
+    struct Holder(#[allow(dead_code)] Sender<Holder>);
+    let (s, r) = unbounded();
+    s.send(Holder(s.clone())).unwrap();
+    assert!(r.close());
+    assert_eq!(s.sender_count(), 0);
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let (s, r) = unbounded();
+    for i in 1..=10 {
+        s.send(DropTester::new(counter.clone(), i)).unwrap();
+    }
+    let items = r.close_and_drain();
+    assert_eq!(counter.load(Ordering::SeqCst), 0);
+    assert_eq!(items.len(), 10);
+    drop(items);
+    assert_eq!(counter.load(Ordering::SeqCst), 10);
+}
+
+#[test]
+fn weak_endpoints() {
+    let (s, r) = bounded::<u64>(1);
+    let weak_s = s.downgrade();
+    let weak_r = r.downgrade();
+    assert_eq!(s.sender_count(), 1);
+    assert_eq!(s.receiver_count(), 1);
+    let upgraded = weak_s.upgrade().unwrap();
+    assert_eq!(s.sender_count(), 2);
+    drop(upgraded);
+    assert_eq!(format!("{:?}", weak_s), "WeakSender { .. }");
+
+    // weak handles don't keep the receiving side open
+    drop(r);
+    assert!(weak_r.upgrade().is_none());
+    assert_eq!(s.send(1), Err(SendError::ReceiveClosed(1)));
+    assert!(weak_s.upgrade().is_some());
+
+    let (s, r) = bounded::<u64>(1);
+    let weak_s = s.downgrade();
+    let h = std::thread::spawn(move || r.recv());
+    drop(s);
+    assert_eq!(h.join().unwrap(), Err(ReceiveError::SendClosed));
+    assert!(weak_s.upgrade().is_none());
+    assert!(weak_s.clone().upgrade().is_none());
+}
+
+#[test]
+fn state_snapshot() {
+    let (s, r) = bounded_with_overflow::<u64>(2, OverflowPolicy::DropOldest);
+    for i in 0..5 {
+        s.send(i).unwrap();
+        assert_eq!(r.len(), (i as usize + 1).min(2));
+    }
+    assert!(r.is_full());
+    let h = {
+        let s = s.clone();
+        let r = r.clone();
+        std::thread::spawn(move || {
+            assert_eq!(r.sender_count(), 2);
+            assert_eq!(r.receiver_count(), 2);
+            drop(s);
+        })
+    };
+    h.join().unwrap();
+    assert_eq!(s.sender_count(), 1);
+    assert_eq!(s.receiver_count(), 1);
+    drop(s);
+    assert!(r.is_disconnected());
+    assert!(!r.is_terminated());
+    assert_eq!(r.recv().unwrap(), 3);
+    assert_eq!(r.recv().unwrap(), 4);
+    assert!(r.is_empty());
+    assert!(r.is_terminated());
+    assert!(!r.is_closed());
+    r.close();
+    assert!(r.is_closed());
+}
+
+#[test]
+fn timeout_wakes_from_sleep() {
+    let (s, r) = new::<u64>(Some(0));
+    let (s2, r2) = (s.clone(), r.clone());
+    let h = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        s2.send(1).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        r2.recv().unwrap()
+    });
+    let start = std::time::Instant::now();
+    // waiters sleep on the os after a short spin, and peers wake them before the deadline
+    assert_eq!(r.recv_timeout(Duration::from_secs(10)), Ok(1));
+    assert_eq!(s.send_timeout(2, Duration::from_secs(10)), Ok(()));
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(h.join().unwrap(), 2);
+}
+
+#[test]
+fn timeout_races_with_peer() {
+    let (s, r) = new::<usize>(Some(0));
+    let h = std::thread::spawn(move || {
+        let mut sent = Vec::new();
+        for i in 0..500 {
+            if s.send_timeout(i, Duration::from_micros(i as u64 % 50 * 10))
+                .is_ok()
+            {
+                sent.push(i);
+            }
+        }
+        sent
+    });
+    let mut received = Vec::new();
+    let mut i = 0_u64;
+    loop {
+        i += 1;
+        match r.recv_timeout(Duration::from_micros(i % 30 * 10)) {
+            Ok(v) => received.push(v),
+            Err(ReceiveErrorTimeout::Timeout) => {}
+            Err(err) => {
+                assert_eq!(err, ReceiveErrorTimeout::SendClosed);
+                break;
+            }
+        }
+    }
+    assert_eq!(h.join().unwrap(), received);
+}
+
 #[test]
 fn vec_test() {
     mpmc_dyn!(vec![1, 2, 3], Some(1));